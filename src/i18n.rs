@@ -0,0 +1,375 @@
+//! Message catalog for user-facing CLI output, selected by `MEM_LANG`.
+//!
+//! Only human-facing reports go through here; JSON hook output and anything
+//! injected into Claude's context stay in English.
+
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    PtBr,
+    Es,
+}
+
+impl Lang {
+    /// Accepts locale-style values: `pt-BR`, `pt_BR.UTF-8`, `es`, `es_MX`, …
+    /// Unknown values fall back to English.
+    pub fn parse(value: &str) -> Lang {
+        let lower = value.trim().to_lowercase();
+        let base = lower.split(['-', '_', '.']).next().unwrap_or("");
+        match base {
+            "pt" => Lang::PtBr,
+            "es" => Lang::Es,
+            _ => Lang::En,
+        }
+    }
+}
+
+/// The language selected by `MEM_LANG`, read once per process.
+pub fn current() -> Lang {
+    static LANG: OnceLock<Lang> = OnceLock::new();
+    *LANG.get_or_init(|| {
+        std::env::var("MEM_LANG")
+            .map(|v| Lang::parse(&v))
+            .unwrap_or(Lang::En)
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Msg {
+    StatusBinary,
+    StatusHook,
+    StatusRule,
+    StatusPayload,
+    StatusIndexed,
+    Installed,
+    NotInstalled,
+    MalformedSettings,
+    IndexedFiles,
+    PayloadNone,
+    PayloadKnown,
+    PayloadDrift,
+    HookRunsNone,
+    HookRunsHeader,
+    Ago,
+    RunOk,
+    RunFailed,
+    IndexSummary,
+    IndexErrors,
+    IndexTruncated,
+    ReportProject,
+    ReportAge,
+    ReportLines,
+    ReportOverGuidance,
+    SearchNoIndex,
+    SearchNoMatches,
+    SearchDidYouMean,
+    SearchExcerpt,
+    SearchPartial,
+    ReaderSearchHeader,
+    ReaderSearchLine,
+    ReaderReportRow,
+    ReaderReportTrend,
+    ExportedTo,
+    ImportSummary,
+    ImportRunIndex,
+    ScheduleInstalled,
+    ScheduleLog,
+    ScheduleEntry,
+    ScheduleLastRun,
+    ScheduleNever,
+    ScheduleNone,
+    ScheduleNothingToRemove,
+    ScheduleRemoved,
+    ReaderHookRun,
+    ReaderHookError,
+}
+
+/// The message in the `MEM_LANG` language.
+pub fn text(msg: Msg) -> &'static str {
+    text_in(current(), msg)
+}
+
+/// The message with `{0}`, `{1}`, … replaced by `args`.
+pub fn fill(msg: Msg, args: &[&dyn Display]) -> String {
+    fill_in(current(), msg, args)
+}
+
+/// `fill` for an explicit language, so formatters can be tested without
+/// depending on the environment.
+pub fn fill_in(lang: Lang, msg: Msg, args: &[&dyn Display]) -> String {
+    fill_template(text_in(lang, msg), args)
+}
+
+/// One left-to-right pass, so `{n}` inside a substituted value stays literal.
+/// Unknown or out-of-range placeholders are kept as written.
+fn fill_template(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let arg = after.find('}').and_then(|close| {
+            let index: usize = after[..close].parse().ok()?;
+            Some((args.get(index)?, close))
+        });
+        match arg {
+            Some((arg, close)) => {
+                out.push_str(&arg.to_string());
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The message in `lang`.
+pub fn text_in(lang: Lang, msg: Msg) -> &'static str {
+    let [en, pt_br, es] = match msg {
+        Msg::StatusBinary => ["Binary", "Binário", "Binario"],
+        Msg::StatusHook => ["Hook", "Hook", "Hook"],
+        Msg::StatusRule => ["Rule", "Regra", "Regla"],
+        Msg::StatusPayload => ["Payload", "Payload", "Payload"],
+        Msg::StatusIndexed => ["Indexed", "Indexados", "Indexados"],
+        Msg::Installed => ["installed", "instalado", "instalado"],
+        Msg::NotInstalled => [
+            "NOT installed — run `mem init`",
+            "NÃO instalado — execute `mem init`",
+            "NO instalado — ejecuta `mem init`",
+        ],
+        Msg::MalformedSettings => [
+            "malformed settings.json",
+            "settings.json malformado",
+            "settings.json mal formado",
+        ],
+        Msg::IndexedFiles => [
+            "{0} MEMORY.md file(s)",
+            "{0} arquivo(s) MEMORY.md",
+            "{0} archivo(s) MEMORY.md",
+        ],
+        Msg::PayloadNone => [
+            "no session-start payload seen yet",
+            "nenhum payload de session-start recebido ainda",
+            "aún no se recibió ningún payload de session-start",
+        ],
+        Msg::PayloadKnown => [
+            "matches known fields",
+            "corresponde aos campos conhecidos",
+            "coincide con los campos conocidos",
+        ],
+        Msg::PayloadDrift => [
+            "{0} unknown field(s): {1} — hook protocol may have changed",
+            "{0} campo(s) desconhecido(s): {1} — o protocolo do hook pode ter mudado",
+            "{0} campo(s) desconocido(s): {1} — el protocolo del hook puede haber cambiado",
+        ],
+        Msg::HookRunsNone => [
+            "No hook runs recorded yet.",
+            "Nenhuma execução de hook registrada ainda.",
+            "Aún no hay ejecuciones de hook registradas.",
+        ],
+        Msg::HookRunsHeader => [
+            "Recent hook runs:",
+            "Execuções recentes de hooks:",
+            "Ejecuciones recientes de hooks:",
+        ],
+        Msg::Ago => ["{0} ago", "há {0}", "hace {0}"],
+        Msg::RunOk => ["ok", "ok", "ok"],
+        Msg::RunFailed => ["FAILED (exit {0})", "FALHOU (saída {0})", "FALLÓ (salida {0})"],
+        Msg::IndexSummary => [
+            "Indexed: {0} new, {1} updated, {2} unchanged, {3} pruned{4} ({5} total)",
+            "Indexado: {0} novo(s), {1} atualizado(s), {2} inalterado(s), {3} removido(s){4} ({5} no total)",
+            "Indexado: {0} nuevo(s), {1} actualizado(s), {2} sin cambios, {3} eliminado(s){4} ({5} en total)",
+        ],
+        Msg::IndexErrors => [", {0} errors", ", {0} erro(s)", ", {0} error(es)"],
+        Msg::IndexTruncated => [
+            ", {0} truncated",
+            ", {0} truncado(s)",
+            ", {0} truncado(s)",
+        ],
+        Msg::ReportProject => ["Project", "Projeto", "Proyecto"],
+        Msg::ReportAge => ["Age", "Idade", "Edad"],
+        Msg::ReportLines => ["Lines", "Linhas", "Líneas"],
+        Msg::ReportOverGuidance => [
+            "over {0}-line guidance",
+            "acima do limite de {0} linhas",
+            "supera el límite de {0} líneas",
+        ],
+        Msg::SearchNoIndex => [
+            "No files indexed. Run `mem index` first.",
+            "Nenhum arquivo indexado. Execute `mem index` primeiro.",
+            "No hay archivos indexados. Ejecuta `mem index` primero.",
+        ],
+        Msg::SearchNoMatches => [
+            "No matches for: {0}",
+            "Nenhum resultado para: {0}",
+            "Sin resultados para: {0}",
+        ],
+        Msg::SearchDidYouMean => [
+            "Did you mean: {0}",
+            "Você quis dizer: {0}",
+            "Quizás quisiste decir: {0}",
+        ],
+        Msg::SearchExcerpt => [
+            " (excerpt — file truncated in the index, line numbers omitted)",
+            " (trecho — arquivo truncado no índice, sem números de linha)",
+            " (extracto — archivo truncado en el índice, sin números de línea)",
+        ],
+        Msg::SearchPartial => [
+            "{0} truncated file(s) could not be read; only their indexed excerpts were searched.",
+            "{0} arquivo(s) truncado(s) não puderam ser lidos; só os trechos indexados foram pesquisados.",
+            "No se pudieron leer {0} archivo(s) truncado(s); solo se buscó en sus extractos indexados.",
+        ],
+        Msg::ReaderSearchHeader => [
+            "Project {0}, file {1}",
+            "Projeto {0}, arquivo {1}",
+            "Proyecto {0}, archivo {1}",
+        ],
+        Msg::ReaderSearchLine => ["Line {0}: {1}", "Linha {0}: {1}", "Línea {0}: {1}"],
+        Msg::ReaderReportRow => [
+            "{0}: modified {1} ago, {2} lines",
+            "{0}: modificado há {1}, {2} linhas",
+            "{0}: modificado hace {1}, {2} líneas",
+        ],
+        Msg::ReaderReportTrend => [
+            ", {0} since last index",
+            ", {0} desde a última indexação",
+            ", {0} desde la última indexación",
+        ],
+        Msg::ExportedTo => [
+            "Exported {0} file(s) to {1}",
+            "{0} arquivo(s) exportado(s) para {1}",
+            "{0} archivo(s) exportado(s) a {1}",
+        ],
+        Msg::ImportSummary => [
+            "Imported: {0} new, {1} updated, {2} skipped, {3} rejected",
+            "Importado: {0} novo(s), {1} atualizado(s), {2} ignorado(s), {3} rejeitado(s)",
+            "Importado: {0} nuevo(s), {1} actualizado(s), {2} omitido(s), {3} rechazado(s)",
+        ],
+        Msg::ImportRunIndex => [
+            "Run `mem index` to make them searchable.",
+            "Execute `mem index` para torná-los pesquisáveis.",
+            "Ejecuta `mem index` para poder buscarlos.",
+        ],
+        Msg::ScheduleInstalled => [
+            "Scheduled weekly `mem index` (Mondays 09:00)",
+            "`mem index` agendado semanalmente (segundas às 09:00)",
+            "`mem index` programado semanalmente (lunes a las 09:00)",
+        ],
+        Msg::ScheduleLog => ["Log: {0}", "Log: {0}", "Registro: {0}"],
+        Msg::ScheduleEntry => ["Scheduled: {0}", "Agendado: {0}", "Programado: {0}"],
+        Msg::ScheduleLastRun => [
+            "Last run: {0}",
+            "Última execução: {0}",
+            "Última ejecución: {0}",
+        ],
+        Msg::ScheduleNever => ["never", "nunca", "nunca"],
+        Msg::ScheduleNone => [
+            "Not scheduled — run `mem schedule install`",
+            "Não agendado — execute `mem schedule install`",
+            "No programado — ejecuta `mem schedule install`",
+        ],
+        Msg::ScheduleNothingToRemove => [
+            "Not scheduled — nothing to remove",
+            "Não agendado — nada a remover",
+            "No programado — nada que eliminar",
+        ],
+        Msg::ScheduleRemoved => [
+            "Removed weekly `mem index` from crontab",
+            "`mem index` semanal removido do crontab",
+            "`mem index` semanal eliminado del crontab",
+        ],
+        Msg::ReaderHookRun => [
+            "{0} ran {1}, took {2} ms, {3}",
+            "{0} executado {1}, levou {2} ms, {3}",
+            "{0} ejecutado {1}, tardó {2} ms, {3}",
+        ],
+        Msg::ReaderHookError => ["Error: {0}", "Erro: {0}", "Error: {0}"],
+    };
+    match lang {
+        Lang::En => en,
+        Lang::PtBr => pt_br,
+        Lang::Es => es,
+    }
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lang_parse_accepts_locale_forms() {
+        assert_eq!(Lang::parse("pt-BR"), Lang::PtBr);
+        assert_eq!(Lang::parse("pt_BR.UTF-8"), Lang::PtBr);
+        assert_eq!(Lang::parse("es"), Lang::Es);
+        assert_eq!(Lang::parse("ES_mx"), Lang::Es);
+        assert_eq!(Lang::parse("en_US"), Lang::En);
+        assert_eq!(Lang::parse("fr"), Lang::En);
+        assert_eq!(Lang::parse(""), Lang::En);
+    }
+
+    #[test]
+    fn fill_template_replaces_numbered_placeholders() {
+        assert_eq!(
+            fill_template("{0} of {1}, {0} again", &[&3, &"ten"]),
+            "3 of ten, 3 again"
+        );
+    }
+
+    #[test]
+    fn fill_template_does_not_expand_placeholders_in_arguments() {
+        assert_eq!(
+            fill_template("Project {0}, file {1}", &[&"odd{1}name", &"/p/MEMORY.md"]),
+            "Project odd{1}name, file /p/MEMORY.md"
+        );
+        assert_eq!(fill_template("{x} {9} {", &[&1]), "{x} {9} {");
+    }
+
+    #[test]
+    fn translations_keep_placeholders() {
+        for msg in [
+            Msg::IndexedFiles,
+            Msg::PayloadDrift,
+            Msg::Ago,
+            Msg::RunFailed,
+            Msg::IndexSummary,
+            Msg::IndexErrors,
+            Msg::IndexTruncated,
+            Msg::ReportOverGuidance,
+            Msg::SearchNoMatches,
+            Msg::SearchDidYouMean,
+            Msg::SearchPartial,
+            Msg::ReaderSearchHeader,
+            Msg::ReaderSearchLine,
+            Msg::ReaderReportRow,
+            Msg::ReaderReportTrend,
+            Msg::ReaderHookRun,
+            Msg::ReaderHookError,
+            Msg::ExportedTo,
+            Msg::ImportSummary,
+            Msg::ScheduleLog,
+            Msg::ScheduleEntry,
+            Msg::ScheduleLastRun,
+        ] {
+            let en = text_in(Lang::En, msg);
+            for lang in [Lang::PtBr, Lang::Es] {
+                for i in 0..6 {
+                    let placeholder = format!("{{{i}}}");
+                    assert_eq!(
+                        en.contains(&placeholder),
+                        text_in(lang, msg).contains(&placeholder),
+                        "{msg:?} {lang:?} {placeholder}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Session-start message assembly: typed sections rendered in the order listed
+//! in `~/.mem/config.json`, then trimmed to the token budget.
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Joins sections of the session-start message.
+const SECTION_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Section names as written in the `sections` config key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    Project,
+    Global,
+    RecentProjects,
+}
+
+impl SectionKind {
+    /// Project context first — it is the most specific and survives trimming.
+    pub const DEFAULT_ORDER: [SectionKind; 3] = [
+        SectionKind::Project,
+        SectionKind::Global,
+        SectionKind::RecentProjects,
+    ];
+}
+
+#[derive(Debug)]
+pub enum Section {
+    /// The project's MEMORY.md
+    Project { path: PathBuf, content: String },
+    /// `~/.claude/MEMORY.md`
+    Global { content: String },
+    /// One line per recently updated project, used outside any project
+    RecentProjects { lines: Vec<String> },
+}
+
+impl Section {
+    pub fn kind(&self) -> SectionKind {
+        match self {
+            Section::Project { .. } => SectionKind::Project,
+            Section::Global { .. } => SectionKind::Global,
+            Section::RecentProjects { .. } => SectionKind::RecentProjects,
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            Section::Project { path, content } => {
+                format!("# Project Memory (`{}`)\n\n{content}", path.display())
+            }
+            Section::Global { content } => format!("# Global Memory\n\n{content}"),
+            Section::RecentProjects { lines } => format!(
+                "# Recent Projects\n\nNo project memory for this directory. \
+                 Recently updated MEMORY.md files:\n\n{}",
+                lines.join("\n")
+            ),
+        }
+    }
+}
+
+/// Render `sections` in `order`, leaving out kinds `order` doesn't list, and
+/// trim the result to `token_budget` (0 = unlimited). `None` when there is
+/// nothing to inject.
+pub fn render(sections: &[Section], order: &[SectionKind], token_budget: usize) -> Option<String> {
+    let mut seen: Vec<SectionKind> = Vec::new();
+    let mut parts: Vec<String> = Vec::new();
+    for kind in order {
+        if seen.contains(kind) {
+            continue;
+        }
+        seen.push(*kind);
+        parts.extend(
+            sections
+                .iter()
+                .filter(|s| s.kind() == *kind)
+                .map(Section::render),
+        );
+    }
+
+    let message = parts.join(SECTION_SEPARATOR);
+    let message = if token_budget == 0 || estimate_tokens(&message) <= token_budget {
+        message
+    } else {
+        eprintln!("mem: session memory trimmed to fit token_budget ({token_budget})");
+        fit_to_budget(parts, token_budget).join(SECTION_SEPARATOR)
+    };
+    (!message.is_empty()).then_some(message)
+}
+
+// ── token budget ──────────────────────────────────────────────────────────────
+
+/// Rough token count (~4 characters per token). Good enough to keep the
+/// injected context bounded without pulling in a tokenizer.
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Keep sections in priority order until `budget` tokens are used. The section
+/// that crosses the limit is cut at a line boundary with an omission marker;
+/// anything after it is dropped.
+fn fit_to_budget(parts: Vec<String>, budget: usize) -> Vec<String> {
+    let sep_cost = estimate_tokens(SECTION_SEPARATOR);
+    let mut remaining = budget;
+    let mut kept: Vec<String> = Vec::new();
+    for part in parts {
+        let sep = if kept.is_empty() { 0 } else { sep_cost };
+        let cost = estimate_tokens(&part) + sep;
+        if cost <= remaining {
+            remaining -= cost;
+            kept.push(part);
+            continue;
+        }
+        if let Some(cut) = truncate_to_tokens(&part, remaining.saturating_sub(sep)) {
+            kept.push(cut);
+        }
+        break;
+    }
+    kept
+}
+
+fn truncate_to_tokens(text: &str, budget: usize) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut available = budget.checked_sub(estimate_tokens(&omitted_marker(lines.len())))?;
+    let mut keep = 0;
+    for line in &lines {
+        // +1 for the newline; per-line rounding keeps the estimate conservative
+        let cost = estimate_tokens(line) + 1;
+        if cost > available {
+            break;
+        }
+        available -= cost;
+        keep += 1;
+    }
+    if keep == 0 {
+        return None;
+    }
+    Some(format!(
+        "{}\n{}",
+        lines[..keep].join("\n"),
+        omitted_marker(lines.len() - keep)
+    ))
+}
+
+fn omitted_marker(lines: usize) -> String {
+    format!("[… {lines} line(s) omitted to fit token_budget]")
+}
+
+// ── tests ─────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_sections() -> Vec<Section> {
+        vec![
+            Section::Global {
+                content: "- Prefer small PRs".to_string(),
+            },
+            Section::Project {
+                path: PathBuf::from("/repo/MEMORY.md"),
+                content: "- Auth: JWT, not sessions".to_string(),
+            },
+            Section::RecentProjects {
+                lines: vec!["- api (2d ago): Auth: JWT".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn render_default_order_snapshot() {
+        let out = render(&sample_sections(), &SectionKind::DEFAULT_ORDER, 0).unwrap();
+        assert_eq!(
+            out,
+            "# Project Memory (`/repo/MEMORY.md`)\n\
+             \n\
+             - Auth: JWT, not sessions\n\
+             \n\
+             ---\n\
+             \n\
+             # Global Memory\n\
+             \n\
+             - Prefer small PRs\n\
+             \n\
+             ---\n\
+             \n\
+             # Recent Projects\n\
+             \n\
+             No project memory for this directory. Recently updated MEMORY.md files:\n\
+             \n\
+             - api (2d ago): Auth: JWT"
+        );
+    }
+
+    #[test]
+    fn render_respects_custom_order_and_disabled_sections() {
+        let order = [
+            SectionKind::Global,
+            SectionKind::Project,
+            SectionKind::Global,
+        ];
+        let out = render(&sample_sections(), &order, 0).unwrap();
+        assert_eq!(
+            out,
+            "# Global Memory\n\
+             \n\
+             - Prefer small PRs\n\
+             \n\
+             ---\n\
+             \n\
+             # Project Memory (`/repo/MEMORY.md`)\n\
+             \n\
+             - Auth: JWT, not sessions"
+        );
+    }
+
+    #[test]
+    fn render_returns_none_when_nothing_enabled() {
+        assert!(render(&sample_sections(), &[], 0).is_none());
+        assert!(render(&[], &SectionKind::DEFAULT_ORDER, 0).is_none());
+    }
+
+    #[test]
+    fn section_kind_parses_snake_case() {
+        let kinds: Vec<SectionKind> =
+            serde_json::from_str(r#"["recent_projects","global"]"#).unwrap();
+        assert_eq!(
+            kinds,
+            vec![SectionKind::RecentProjects, SectionKind::Global]
+        );
+    }
+
+    #[test]
+    fn estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn fit_to_budget_keeps_everything_under_budget() {
+        let parts = vec![
+            "# Project\n\n- a".to_string(),
+            "# Global\n\n- b".to_string(),
+        ];
+        assert_eq!(fit_to_budget(parts.clone(), 1000), parts);
+    }
+
+    #[test]
+    fn fit_to_budget_truncates_and_drops_overflow() {
+        let project = (0..50)
+            .map(|i| format!("- decision number {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parts = vec![project, "# Global\n\n- b".to_string()];
+        let kept = fit_to_budget(parts, 60);
+        assert_eq!(kept.len(), 1);
+        assert!(kept[0].starts_with("- decision number 0"));
+        assert!(kept[0].contains("omitted to fit token_budget"));
+        assert!(estimate_tokens(&kept[0]) <= 60);
+    }
+}
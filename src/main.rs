@@ -1,10 +1,16 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
-use std::io::{IsTerminal, Read};
+use std::io::{IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+mod i18n;
+mod inject;
+
+use i18n::{Lang, Msg};
+use inject::{Section, SectionKind};
+
 // ── CLI ───────────────────────────────────────────────────────────────────────
 
 #[derive(Parser)]
@@ -12,6 +18,15 @@ use std::process::{Command, Stdio};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Plain-text output for screen readers: no box-drawing, symbols or
+    /// alignment-only columns
+    #[arg(long, global = true)]
+    screen_reader: bool,
+
+    /// Index file to use instead of ~/.mem/index.json (also: MEM_INDEX_PATH)
+    #[arg(long, global = true, value_name = "PATH")]
+    index: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -25,14 +40,93 @@ enum Commands {
         project: Option<PathBuf>,
     },
 
+    /// Print the full MEMORY.md that session-start injects for a project
+    ShowFile {
+        #[arg(long)]
+        project: Option<PathBuf>,
+        /// Also copy the file to the system clipboard
+        #[arg(long)]
+        copy: bool,
+    },
+
     /// Show hook install state and indexed file count
-    Status,
+    Status {
+        /// Also list recent session-start runs and their outcome
+        #[arg(long)]
+        hooks: bool,
+    },
 
     /// Index all MEMORY.md files for search
-    Index,
+    Index {
+        /// Print each file's age, line count and trend after indexing
+        #[arg(long)]
+        report: bool,
+    },
 
     /// Search across indexed MEMORY.md files
-    Search { query: String },
+    Search {
+        query: String,
+        /// Also copy the results to the system clipboard
+        #[arg(long)]
+        copy: bool,
+    },
+
+    /// Export indexed MEMORY.md files as JSON or Markdown
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Only projects whose label contains this text
+        #[arg(long)]
+        project: Option<String>,
+        /// Write one file per project into this directory instead of one
+        /// archive on stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Restore MEMORY.md files from a `mem export --format json` archive
+    Import {
+        /// Archive file, or a directory of per-project `.json` files
+        path: PathBuf,
+        /// Rewrite source paths starting with OLD to start with NEW
+        /// (e.g. a different home directory)
+        #[arg(long, value_name = "OLD=NEW")]
+        remap: Vec<String>,
+        /// Replace existing files whose content differs
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Run `mem index` weekly from the user crontab
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleAction {
+    /// Add (or refresh) the weekly crontab entry
+    Install,
+    /// Show the crontab entry and when it last ran
+    Status,
+    /// Remove the crontab entry
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
 }
 
 // ── Types ─────────────────────────────────────────────────────────────────────
@@ -42,36 +136,275 @@ struct HookStdin {
     pub cwd: Option<String>,
 }
 
+/// Shape of the last hook payload session-start received, kept in
+/// `~/.mem/hook_schema.json`. Claude Code payloads carry no version field, so
+/// the sorted field list is the schema fingerprint.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct HookSchema {
+    pub fields: Vec<String>,
+    /// Fields not in `KNOWN_HOOK_FIELDS` — a sign the protocol has drifted
+    pub unknown: Vec<String>,
+    /// Unix day of the last unknown-field warning, so it is logged once per day
+    pub warned_day: i64,
+}
+
+/// One hook invocation, kept in `~/.mem/hook_runs.json` for postmortems.
+#[derive(Debug, Serialize, Deserialize)]
+struct HookRun {
+    pub hook: String,
+    /// Unix seconds
+    pub started_at: i64,
+    pub duration_ms: u64,
+    /// Process exit code mem returned to Claude Code (0 or 1)
+    pub exit_code: i32,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct SessionStartOutput {
     #[serde(rename = "systemMessage")]
     pub system_message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct IndexEntry {
     pub project: String,
     pub path: String,
     pub content: String,
     /// Unix mtime seconds — used to skip unchanged files on re-index
     pub mtime: i64,
+    /// Line count before the last content change, for the `--report` trend
+    #[serde(default)]
+    pub prev_lines: Option<usize>,
+    /// `content` is a head/headings/tail excerpt (see `max_index_bytes`), not
+    /// the whole file
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Line count of the whole source file, which `content` lacks when
+    /// truncated. `None` in indexes written before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<usize>,
+}
+
+impl IndexEntry {
+    fn line_count(&self) -> usize {
+        self.lines.unwrap_or_else(|| self.content.lines().count())
+    }
+}
+
+/// User settings from `~/.mem/config.json`. Missing keys take their defaults.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Approximate token ceiling for the session-start message (0 = unlimited)
+    pub token_budget: usize,
+    /// Line cap for the injected project MEMORY.md (0 = unlimited)
+    pub max_lines: usize,
+    /// Byte cap for the injected project MEMORY.md (0 = unlimited)
+    pub max_bytes: usize,
+    /// Byte cap for a MEMORY.md stored in the search index (0 = unlimited)
+    pub max_index_bytes: usize,
+    /// Session-start sections in injection order; unlisted sections are skipped
+    pub sections: Vec<SectionKind>,
+    /// Attribution stamped on `mem export` output
+    pub ownership: Ownership,
+}
+
+/// Author / org / license recorded on exported files for compliance reviews.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Ownership {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
+impl Ownership {
+    /// `Author: … · Org: … · License: …` for the fields that are set.
+    fn attribution(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            ("Author", &self.author),
+            ("Org", &self.org),
+            ("License", &self.license),
+        ]
+        .iter()
+        .filter_map(|(label, value)| value.as_ref().map(|v| format!("{label}: {v}")))
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            token_budget: DEFAULT_TOKEN_BUDGET,
+            max_lines: DEFAULT_MAX_LINES,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_index_bytes: DEFAULT_MAX_INDEX_BYTES,
+            sections: SectionKind::DEFAULT_ORDER.to_vec(),
+            ownership: Ownership::default(),
+        }
+    }
+}
+
+/// One indexed MEMORY.md as written by `mem export --format json`.
+#[derive(Debug, Serialize)]
+struct ExportEntry<'a> {
+    pub project: &'a str,
+    pub source_path: &'a str,
+    /// Unix mtime seconds of the source file when it was indexed
+    pub modified_at: i64,
+    pub content: &'a str,
+    #[serde(flatten)]
+    pub ownership: &'a Ownership,
+}
+
+/// `manifest.json` written next to per-project files by `mem export --out`.
+#[derive(Debug, Serialize)]
+struct ExportManifest<'a> {
+    /// Unix seconds
+    pub generated_at: i64,
+    pub mem_version: &'static str,
+    #[serde(flatten)]
+    pub ownership: &'a Ownership,
+    pub files: Vec<ManifestFile<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestFile<'a> {
+    pub project: &'a str,
+    /// File name inside the export directory
+    pub file: String,
+    pub source_path: &'a str,
+    pub modified_at: i64,
+    pub lines: usize,
+}
+
+/// One record of an export archive read back by `mem import`.
+#[derive(Debug, Deserialize)]
+struct ImportEntry {
+    pub project: String,
+    pub source_path: String,
+    pub content: String,
+}
+
+/// Outcome counts for `mem import`.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ImportStats {
+    pub new: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub rejected: usize,
+}
+
+/// A single matching line returned by `search_index`.
+#[derive(Debug)]
+struct SearchHit<'a> {
+    pub project: &'a str,
+    pub path: &'a str,
+    /// 1-based line number within `path`; `None` when the index only holds an
+    /// excerpt of the file, whose line numbers would not match the source
+    pub line_no: Option<usize>,
+    pub line: &'a str,
 }
 
 // ── Entry point ───────────────────────────────────────────────────────────────
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    // Pinned to the directory mem was started in, so the hook and cron — which
+    // run elsewhere — read the same file.
+    let index_flag = cli.index.map(absolute);
+    let index = resolve_index_path(
+        index_flag.clone(),
+        std::env::var_os("MEM_INDEX_PATH"),
+        dirs::home_dir(),
+    )
+    .map(absolute);
+    let index = index.as_deref();
     match cli.command {
-        Commands::Init => cmd_init(),
-        Commands::SessionStart { project } => cmd_session_start(project),
-        Commands::Status => cmd_status(),
-        Commands::Index => cmd_index(),
-        Commands::Search { query } => cmd_search(query),
+        Commands::Init => cmd_init(index_flag.as_deref()),
+        Commands::SessionStart { project } => {
+            let started = std::time::Instant::now();
+            let result = cmd_session_start(project, index);
+            record_hook_run("session-start", started.elapsed(), &result);
+            result
+        }
+        Commands::ShowFile { project, copy } => cmd_show_file(project, copy),
+        Commands::Status { hooks } => cmd_status(hooks, index, cli.screen_reader),
+        Commands::Index { report } => cmd_index(report, index, cli.screen_reader),
+        Commands::Search { query, copy } => cmd_search(query, copy, index, cli.screen_reader),
+        Commands::Export {
+            format,
+            project,
+            out,
+        } => cmd_export(format, project, out, index, cli.screen_reader),
+        Commands::Import {
+            path,
+            remap,
+            overwrite,
+        } => cmd_import(path, remap, overwrite, cli.screen_reader),
+        Commands::Schedule { action } => cmd_schedule(action, index, cli.screen_reader),
     }
 }
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
+const DEFAULT_TOKEN_BUDGET: usize = 4000;
+const DEFAULT_MAX_LINES: usize = 200;
+const DEFAULT_MAX_BYTES: usize = 16 * 1024;
+const DEFAULT_MAX_INDEX_BYTES: usize = 256 * 1024;
+
+/// SessionStart payload fields as documented by Claude Code. Anything else is
+/// reported as drift; only `cwd` is actually used.
+const KNOWN_HOOK_FIELDS: &[&str] = &[
+    "cwd",
+    "hook_event_name",
+    "permission_mode",
+    "session_id",
+    "source",
+    "transcript_path",
+];
+
+/// Ignored in unquoted search terms — they match nearly every line.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on",
+    "or", "that", "the", "to", "was", "with",
+];
+
+/// Trailing comment identifying the crontab line `mem schedule` manages.
+const SCHEDULE_MARKER: &str = "# mem schedule";
+/// Mondays at 09:00 local time.
+const SCHEDULE_WHEN: &str = "0 9 * * 1";
+
+/// Bundle summary written by `mem export --out`; skipped by `mem import`.
+const EXPORT_MANIFEST: &str = "manifest.json";
+
+/// Clipboard writers tried in order; the first one installed wins.
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// Hook runs kept in `~/.mem/hook_runs.json`; older ones are dropped.
+const HOOK_RUNS_KEPT: usize = 50;
+/// Hook runs shown by `mem status --hooks`.
+const HOOK_RUNS_SHOWN: usize = 10;
+
+/// How many projects the outside-a-project fallback lists.
+const RECENT_PROJECTS: usize = 5;
+const SUMMARY_CHARS: usize = 100;
+
+/// Line limit the CLAUDE.md rule asks Claude to keep MEMORY.md under.
+const MEMORY_LINE_GUIDANCE: usize = 30;
+
 const CLAUDE_MD_MARKER: &str = "## Session Memory (managed by mem)";
 
 const CLAUDE_MD_BLOCK: &str = "\
@@ -86,12 +419,12 @@ Keep it under 30 lines. Rewrite, don't append — remove stale entries.
 
 // ── init ──────────────────────────────────────────────────────────────────────
 
-fn cmd_init() -> Result<()> {
+fn cmd_init(index_flag: Option<&Path>) -> Result<()> {
     let home = dirs::home_dir().context("$HOME not set")?;
 
     let mut added: Vec<&str> = Vec::new();
 
-    if wire_session_start_hook(&home.join(".claude").join("settings.json"))? {
+    if wire_session_start_hook(&home.join(".claude").join("settings.json"), index_flag)? {
         added.push("SessionStart hook → ~/.claude/settings.json");
     }
     if wire_claude_md(&home.join(".claude").join("CLAUDE.md"))? {
@@ -111,9 +444,11 @@ fn cmd_init() -> Result<()> {
     Ok(())
 }
 
-fn wire_session_start_hook(settings_path: &Path) -> Result<bool> {
+/// Install the SessionStart hook. With `index`, the hook reads that index —
+/// Claude Code runs it without mem's `--index` flag otherwise.
+fn wire_session_start_hook(settings_path: &Path, index: Option<&Path>) -> Result<bool> {
     let bin = std::env::current_exe().context("cannot resolve binary path")?;
-    let cmd = format!("{} session-start", bin.display());
+    let cmd = session_start_command(&bin, index);
 
     let raw = if settings_path.exists() {
         std::fs::read_to_string(settings_path)
@@ -144,15 +479,43 @@ fn wire_session_start_hook(settings_path: &Path) -> Result<bool> {
         return Ok(false);
     }
 
-    entry
+    let groups = entry
         .as_array_mut()
-        .context("SessionStart hooks must be an array")?
-        .push(serde_json::json!({"hooks": [{"type": "command", "command": cmd}]}));
+        .context("SessionStart hooks must be an array")?;
+    // Re-running init with a different --index replaces the old hook instead of
+    // injecting memory twice per session.
+    let prefix = format!("{} ", bin.display());
+    for group in groups.iter_mut() {
+        if let Some(hooks) = group.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+            hooks.retain(|h| {
+                !h.get("command")
+                    .and_then(|c| c.as_str())
+                    .is_some_and(|c| c.starts_with(&prefix) && c.ends_with(" session-start"))
+            });
+        }
+    }
+    groups.retain(|g| {
+        !g.get("hooks")
+            .and_then(|h| h.as_array())
+            .is_some_and(|h| h.is_empty())
+    });
+    groups.push(serde_json::json!({"hooks": [{"type": "command", "command": cmd}]}));
 
     atomic_write_json(settings_path, &settings)?;
     Ok(true)
 }
 
+fn session_start_command(bin: &Path, index: Option<&Path>) -> String {
+    match index {
+        Some(index) => format!(
+            "{} --index {} session-start",
+            bin.display(),
+            shell_quote(&index.display().to_string())
+        ),
+        None => format!("{} session-start", bin.display()),
+    }
+}
+
 fn wire_claude_md(path: &Path) -> Result<bool> {
     let existing = if path.exists() {
         std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?
@@ -180,16 +543,18 @@ fn wire_claude_md(path: &Path) -> Result<bool> {
 
 // ── session-start ─────────────────────────────────────────────────────────────
 
-fn cmd_session_start(project_override: Option<PathBuf>) -> Result<()> {
+fn cmd_session_start(project_override: Option<PathBuf>, index: Option<&Path>) -> Result<()> {
     let cwd = resolve_cwd(project_override)?;
-    let mut parts: Vec<String> = Vec::new();
-
-    if let Some((content, path)) = find_memory_md(&cwd) {
-        parts.push(format!(
-            "# Project Memory (`{}`)\n\n{}",
-            path.display(),
-            content.trim()
-        ));
+    let config = load_config();
+    let mut sections: Vec<Section> = Vec::new();
+
+    // One `git rev-parse` per hook run: the root is reused for both checks.
+    let repo_root = git_repo_root(&cwd);
+    let project_memory = find_memory_md_in(&cwd, repo_root.as_deref());
+    let in_project = project_memory.is_some() || repo_root.is_some();
+    if let Some((content, path)) = project_memory {
+        let content = truncate_head_tail(content.trim(), config.max_lines, config.max_bytes);
+        sections.push(Section::Project { path, content });
     }
 
     if let Some(home) = dirs::home_dir() {
@@ -199,7 +564,9 @@ fn cmd_session_start(project_override: Option<PathBuf>) -> Result<()> {
                 Ok(content) => {
                     let trimmed = content.trim();
                     if !trimmed.is_empty() {
-                        parts.push(format!("# Global Memory\n\n{trimmed}"));
+                        sections.push(Section::Global {
+                            content: trimmed.to_string(),
+                        });
                     }
                 }
                 Err(e) => eprintln!("mem: cannot read global memory {}: {e}", global.display()),
@@ -207,48 +574,189 @@ fn cmd_session_start(project_override: Option<PathBuf>) -> Result<()> {
         }
     }
 
-    if parts.is_empty() {
-        return Ok(());
+    // Outside any project (e.g. started from ~): list where work happened lately
+    // so the agent can orient itself instead of starting blind.
+    if !in_project && config.sections.contains(&SectionKind::RecentProjects) {
+        let lines = recent_project_lines(&load_index(index), unix_now(), RECENT_PROJECTS);
+        if !lines.is_empty() {
+            sections.push(Section::RecentProjects { lines });
+        }
     }
 
-    let output = SessionStartOutput {
-        system_message: parts.join("\n\n---\n\n"),
+    let Some(system_message) = inject::render(&sections, &config.sections, config.token_budget)
+    else {
+        return Ok(());
     };
+
+    let output = SessionStartOutput { system_message };
     println!("{}", serde_json::to_string(&output)?);
     Ok(())
 }
 
+/// Most recently modified indexed MEMORY.md files, one line each.
+fn recent_project_lines(index: &[IndexEntry], now: i64, limit: usize) -> Vec<String> {
+    let mut entries: Vec<&IndexEntry> = index.iter().collect();
+    entries.sort_by(|a, b| newest_first(a, b));
+    entries
+        .into_iter()
+        .take(limit)
+        .map(|e| {
+            let age = format_age(now - e.mtime);
+            match one_line_summary(&e.content) {
+                Some(summary) => format!("- {} ({age} ago): {summary}", e.project),
+                None => format!("- {} ({age} ago)", e.project),
+            }
+        })
+        .collect()
+}
+
+/// First non-heading line of a MEMORY.md, without its bullet, capped in length.
+fn one_line_summary(content: &str) -> Option<String> {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))?;
+    let line = line.trim_start_matches(['-', '*', ' ']);
+    if line.chars().count() > SUMMARY_CHARS {
+        Some(line.chars().take(SUMMARY_CHARS).collect::<String>() + "…")
+    } else {
+        Some(line.to_string())
+    }
+}
+
+// ── show-file ─────────────────────────────────────────────────────────────────
+
+fn cmd_show_file(project: Option<PathBuf>, copy: bool) -> Result<()> {
+    let cwd = match project {
+        Some(p) => p,
+        None => std::env::current_dir()?,
+    };
+    let (content, path) = find_memory_md(&cwd)
+        .with_context(|| format!("no MEMORY.md found for {}", cwd.display()))?;
+    eprintln!("{}", path.display());
+    print!("{content}");
+    if copy {
+        copy_to_clipboard(&content)?;
+    }
+    Ok(())
+}
+
 // ── status ────────────────────────────────────────────────────────────────────
 
-fn cmd_status() -> Result<()> {
+fn cmd_status(hooks: bool, index_path: Option<&Path>, screen_reader: bool) -> Result<()> {
     let home = dirs::home_dir().context("$HOME not set")?;
     let bin = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("mem"));
 
-    println!("Binary    : {}", bin.display());
+    let print_status_line = |label: Msg, value: &dyn std::fmt::Display| {
+        let value = for_reader(value.to_string(), screen_reader);
+        // Padding only aligns the columns visually; screen readers skip it.
+        let width = if screen_reader { 0 } else { 10 };
+        println!("{:<width$}: {value}", i18n::text(label));
+    };
+
+    print_status_line(Msg::StatusBinary, &bin.display());
 
     let hook_status = check_session_start_hook(&home.join(".claude").join("settings.json"));
-    println!("Hook      : {hook_status}");
+    print_status_line(Msg::StatusHook, &hook_status);
 
     let rule_status = match std::fs::read_to_string(home.join(".claude").join("CLAUDE.md")) {
-        Ok(c) if c.contains(CLAUDE_MD_MARKER) => "installed",
-        Ok(_) => "NOT installed — run `mem init`",
-        Err(_) => "NOT installed — run `mem init`",
+        Ok(c) if c.contains(CLAUDE_MD_MARKER) => i18n::text(Msg::Installed),
+        Ok(_) => i18n::text(Msg::NotInstalled),
+        Err(_) => i18n::text(Msg::NotInstalled),
     };
-    println!("Rule      : {rule_status}");
+    print_status_line(Msg::StatusRule, &rule_status);
+
+    print_status_line(
+        Msg::StatusPayload,
+        &hook_schema_status(load_hook_schema().as_ref(), i18n::current()),
+    );
 
-    let index = load_index();
-    println!("Indexed   : {} MEMORY.md file(s)", index.len());
+    let index = load_index(index_path);
+    print_status_line(
+        Msg::StatusIndexed,
+        &i18n::fill(Msg::IndexedFiles, &[&index.len()]),
+    );
 
+    if hooks {
+        println!();
+        print!(
+            "{}",
+            format_hook_runs(
+                &load_hook_runs(),
+                unix_now(),
+                screen_reader,
+                i18n::current()
+            )
+        );
+    }
     Ok(())
 }
 
+/// `text` as is, or through `plain_text` in screen-reader mode.
+fn for_reader(text: String, screen_reader: bool) -> String {
+    if screen_reader {
+        plain_text(&text)
+    } else {
+        text
+    }
+}
+
+/// Replace symbols screen readers announce awkwardly with plain punctuation.
+fn plain_text(text: &str) -> String {
+    text.replace(" — ", "; ").replace('…', "...")
+}
+
+/// Most recent hook runs first, one line each, with the error under failures.
+/// With `screen_reader`, each run is a sentence instead of aligned columns.
+fn format_hook_runs(runs: &[HookRun], now: i64, screen_reader: bool, lang: Lang) -> String {
+    if runs.is_empty() {
+        return format!("{}\n", i18n::text_in(lang, Msg::HookRunsNone));
+    }
+    let mut out = format!("{}\n", i18n::text_in(lang, Msg::HookRunsHeader));
+    for run in runs.iter().rev().take(HOOK_RUNS_SHOWN) {
+        let outcome = if run.exit_code == 0 {
+            i18n::text_in(lang, Msg::RunOk).to_string()
+        } else {
+            i18n::fill_in(lang, Msg::RunFailed, &[&run.exit_code])
+        };
+        let ago = i18n::fill_in(lang, Msg::Ago, &[&format_age(now - run.started_at)]);
+        if screen_reader {
+            out.push_str(&i18n::fill_in(
+                lang,
+                Msg::ReaderHookRun,
+                &[&run.hook, &ago, &run.duration_ms, &outcome],
+            ));
+            out.push('\n');
+            if let Some(error) = &run.error {
+                out.push_str(&plain_text(&i18n::fill_in(
+                    lang,
+                    Msg::ReaderHookError,
+                    &[error],
+                )));
+                out.push('\n');
+            }
+            continue;
+        }
+        out.push_str(&format!(
+            "  {ago:>9}  {}  {}ms  {outcome}\n",
+            run.hook, run.duration_ms
+        ));
+        if let Some(error) = &run.error {
+            out.push_str(&format!("             {error}\n"));
+        }
+    }
+    out
+}
+
 // ── index ─────────────────────────────────────────────────────────────────────
 
-fn cmd_index() -> Result<()> {
-    let mut existing = load_index();
+fn cmd_index(report: bool, index_path: Option<&Path>, screen_reader: bool) -> Result<()> {
+    let config = load_config();
+    let mut existing = load_index(index_path);
     let mut new_count = 0usize;
     let mut updated_count = 0usize;
     let mut unchanged_count = 0usize;
+    let mut truncated_count = 0usize;
     let mut error_count = 0usize;
 
     // Collect candidate MEMORY.md paths from ~/.claude/projects/
@@ -293,11 +801,15 @@ fn cmd_index() -> Result<()> {
                 unchanged_count += 1;
                 continue;
             }
-            match std::fs::read_to_string(&path) {
-                Ok(content) => {
-                    entry.content = content;
-                    entry.mtime = mtime;
-                    updated_count += 1;
+            match read_for_index(&path, config.max_index_bytes) {
+                Ok((content, lines, truncated)) => {
+                    truncated_count += usize::from(truncated);
+                    entry.truncated = truncated;
+                    if refresh_entry(entry, content, lines, mtime) {
+                        updated_count += 1;
+                    } else {
+                        unchanged_count += 1;
+                    }
                 }
                 Err(e) => {
                     eprintln!("mem: cannot read {}: {e}", path.display());
@@ -305,13 +817,17 @@ fn cmd_index() -> Result<()> {
                 }
             }
         } else {
-            match std::fs::read_to_string(&path) {
-                Ok(content) => {
+            match read_for_index(&path, config.max_index_bytes) {
+                Ok((content, lines, truncated)) => {
+                    truncated_count += usize::from(truncated);
                     existing.push(IndexEntry {
                         project,
                         path: path_str,
                         content,
                         mtime,
+                        prev_lines: None,
+                        truncated,
+                        lines: Some(lines),
                     });
                     new_count += 1;
                 }
@@ -328,137 +844,1163 @@ fn cmd_index() -> Result<()> {
     existing.retain(|e| std::path::Path::new(&e.path).exists());
     let pruned = before - existing.len();
 
-    save_index(&existing)?;
+    save_index(index_path, &existing)?;
 
+    let mut errors = String::new();
+    if truncated_count > 0 {
+        errors.push_str(&i18n::fill(Msg::IndexTruncated, &[&truncated_count]));
+    }
+    if error_count > 0 {
+        errors.push_str(&i18n::fill(Msg::IndexErrors, &[&error_count]));
+    }
     println!(
-        "Indexed: {} new, {} updated, {} unchanged, {} pruned{} ({} total)",
-        new_count,
-        updated_count,
-        unchanged_count,
-        pruned,
-        if error_count > 0 {
-            format!(", {} errors", error_count)
-        } else {
-            String::new()
-        },
-        existing.len()
+        "{}",
+        i18n::fill(
+            Msg::IndexSummary,
+            &[
+                &new_count,
+                &updated_count,
+                &unchanged_count,
+                &pruned,
+                &errors,
+                &existing.len()
+            ]
+        )
     );
+    if report {
+        println!();
+        print!(
+            "{}",
+            format_index_report(&existing, unix_now(), screen_reader, i18n::current())
+        );
+    }
     if error_count > 0 {
         anyhow::bail!("{error_count} file(s) could not be read");
     }
     Ok(())
 }
 
+/// Read a MEMORY.md for the index, capped at `max_bytes` (0 = unlimited) so one
+/// runaway file can't bloat the index. Returns the content, the file's own line
+/// count, and whether the content was truncated.
+fn read_for_index(path: &Path, max_bytes: usize) -> std::io::Result<(String, usize, bool)> {
+    let content = std::fs::read_to_string(path)?;
+    let lines = content.lines().count();
+    if max_bytes == 0 || content.len() <= max_bytes {
+        return Ok((content, lines, false));
+    }
+    eprintln!(
+        "mem: {} is {} KB; indexing head, headings and tail only (max_index_bytes = {max_bytes})",
+        path.display(),
+        content.len() / 1024
+    );
+    Ok((truncate_head_tail(&content, 0, max_bytes), lines, true))
+}
+
+/// Apply freshly read content to an index entry whose mtime changed. A touch
+/// or checkout bumps mtime without changing the text, so compare the content
+/// itself before treating the file as updated. `lines` is the source's line
+/// count, which catches edits an excerpt leaves out. Returns whether it changed.
+fn refresh_entry(entry: &mut IndexEntry, content: String, lines: usize, mtime: i64) -> bool {
+    entry.mtime = mtime;
+    let changed = entry.content != content || entry.line_count() != lines;
+    if changed {
+        entry.prev_lines = Some(entry.line_count());
+        entry.content = content;
+    }
+    entry.lines = Some(lines);
+    changed
+}
+
+/// One row per indexed file: age since last modification, current line count
+/// with the change since the previous version, and a flag when the file has
+/// outgrown the line guidance.
+fn format_index_report(
+    entries: &[IndexEntry],
+    now: i64,
+    screen_reader: bool,
+    lang: Lang,
+) -> String {
+    let mut rows: Vec<&IndexEntry> = entries.iter().collect();
+    rows.sort_by(|a, b| a.project.cmp(&b.project));
+
+    if screen_reader {
+        let mut out = String::new();
+        for entry in rows {
+            let lines = entry.line_count();
+            out.push_str(&i18n::fill_in(
+                lang,
+                Msg::ReaderReportRow,
+                &[&entry.project, &format_age(now - entry.mtime), &lines],
+            ));
+            if let Some(prev) = entry.prev_lines.filter(|p| *p != lines) {
+                let change = format!("{:+}", lines as i64 - prev as i64);
+                out.push_str(&i18n::fill_in(lang, Msg::ReaderReportTrend, &[&change]));
+            }
+            if lines > MEMORY_LINE_GUIDANCE {
+                out.push_str(", ");
+                out.push_str(&i18n::fill_in(
+                    lang,
+                    Msg::ReportOverGuidance,
+                    &[&MEMORY_LINE_GUIDANCE],
+                ));
+            }
+            out.push('\n');
+        }
+        return out;
+    }
+
+    let header = i18n::text_in(lang, Msg::ReportProject);
+    let width = rows
+        .iter()
+        .map(|e| e.project.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(header.chars().count());
+    let mut out = format!(
+        "{header:<width$}  {:>5}  {:>12}\n",
+        i18n::text_in(lang, Msg::ReportAge),
+        i18n::text_in(lang, Msg::ReportLines)
+    );
+    for entry in rows {
+        let lines = entry.line_count();
+        let trend = match entry.prev_lines {
+            Some(prev) if prev != lines => format!("{lines} ({:+})", lines as i64 - prev as i64),
+            _ => lines.to_string(),
+        };
+        let flag = if lines > MEMORY_LINE_GUIDANCE {
+            format!(
+                "  {}",
+                i18n::fill_in(lang, Msg::ReportOverGuidance, &[&MEMORY_LINE_GUIDANCE])
+            )
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "{:<width$}  {:>5}  {trend:>12}{flag}\n",
+            entry.project,
+            format_age(now - entry.mtime),
+        ));
+    }
+    out
+}
+
+/// Compact age like `45m`, `6h`, `12d`.
+fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
+
 // ── search ────────────────────────────────────────────────────────────────────
 
-fn cmd_search(query: String) -> Result<()> {
-    let index = load_index();
+fn cmd_search(
+    query: String,
+    copy: bool,
+    index_path: Option<&Path>,
+    screen_reader: bool,
+) -> Result<()> {
+    let mut index = load_index(index_path);
 
     if index.is_empty() {
-        println!("No files indexed. Run `mem index` first.");
+        println!("{}", i18n::text(Msg::SearchNoIndex));
         return Ok(());
     }
 
-    let query_lower = query.to_lowercase();
-    let mut found = false;
-
-    for entry in &index {
-        let matches: Vec<&str> = entry
-            .content
-            .lines()
-            .filter(|l| l.to_lowercase().contains(&query_lower))
-            .collect();
-
-        if !matches.is_empty() {
-            println!("── {} ──", entry.project);
-            for line in matches {
-                println!("  {}", line.trim());
-            }
-            println!();
-            found = true;
+    let unreadable = expand_truncated(&mut index);
+    let hits = search_index(&index, &query);
+    if hits.is_empty() {
+        println!("{}", i18n::fill(Msg::SearchNoMatches, &[&query]));
+        if let Some(suggestion) = suggest_query(&index, &query) {
+            println!("{}", i18n::fill(Msg::SearchDidYouMean, &[&suggestion]));
+        }
+        if unreadable > 0 {
+            println!("{}", i18n::fill(Msg::SearchPartial, &[&unreadable]));
         }
+        return Ok(());
     }
 
-    if !found {
-        println!("No matches for: {query}");
+    let rendered = format_search_hits(&hits, screen_reader, i18n::current());
+    print!("{rendered}");
+    if copy {
+        copy_to_clipboard(rendered.trim_end())?;
     }
     Ok(())
 }
 
-// ── index persistence ─────────────────────────────────────────────────────────
+/// Swap truncated excerpts for the whole source file, so search and did-you-mean
+/// see the cut middle too. Returns how many files could not be read; those keep
+/// their excerpt.
+fn expand_truncated(index: &mut [IndexEntry]) -> usize {
+    let mut unreadable = 0;
+    for entry in index.iter_mut().filter(|e| e.truncated) {
+        match full_entry(entry) {
+            Ok(full) => *entry = full,
+            Err(_) => unreadable += 1,
+        }
+    }
+    unreadable
+}
 
-fn index_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".mem").join("index.json"))
+/// Hits grouped under a per-file header. The screen-reader form spells out
+/// what each part is instead of relying on box-drawing and column alignment.
+fn format_search_hits(hits: &[SearchHit], screen_reader: bool, lang: Lang) -> String {
+    let mut out = String::new();
+    let mut current: Option<&str> = None;
+    for hit in hits {
+        if current != Some(hit.path) {
+            if current.is_some() {
+                out.push('\n');
+            }
+            if screen_reader {
+                out.push_str(&i18n::fill_in(
+                    lang,
+                    Msg::ReaderSearchHeader,
+                    &[&hit.project, &hit.path],
+                ));
+            } else {
+                out.push_str(&format!("── {} ── {}", hit.project, hit.path));
+            }
+            if hit.line_no.is_none() {
+                let note = i18n::text_in(lang, Msg::SearchExcerpt);
+                out.push_str(&if screen_reader {
+                    plain_text(note)
+                } else {
+                    note.to_string()
+                });
+            }
+            out.push('\n');
+            current = Some(hit.path);
+        }
+        match (hit.line_no, screen_reader) {
+            (Some(line_no), true) => {
+                out.push_str(&i18n::fill_in(
+                    lang,
+                    Msg::ReaderSearchLine,
+                    &[&line_no, &hit.line.trim()],
+                ));
+                out.push('\n');
+            }
+            (Some(line_no), false) => {
+                out.push_str(&format!("  {line_no:>3}: {}\n", hit.line.trim()));
+            }
+            (None, true) => {
+                out.push_str(hit.line.trim());
+                out.push('\n');
+            }
+            (None, false) => out.push_str(&format!("       {}\n", hit.line.trim())),
+        }
+    }
+    out.push('\n');
+    out
 }
 
-fn load_index() -> Vec<IndexEntry> {
-    let Some(path) = index_path() else {
+/// Every line in the index containing all of `query`'s terms (case-insensitive),
+/// with the file and 1-based line number it came from so the result can be
+/// opened and verified. Files are ordered most recently modified first, then
+/// by path, so results don't depend on the order entries were indexed.
+fn search_index<'a>(index: &'a [IndexEntry], query: &str) -> Vec<SearchHit<'a>> {
+    let terms = query_terms(query);
+    if terms.is_empty() {
         return Vec::new();
-    };
-    let raw = match std::fs::read_to_string(&path) {
-        Ok(r) => r,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
-        Err(e) => {
-            eprintln!("mem: cannot read index {}: {e}", path.display());
-            eprintln!("mem: run `mem index` to rebuild, or check file permissions");
-            return Vec::new();
+    }
+    let terms = terms.as_slice();
+    let mut entries: Vec<&IndexEntry> = index.iter().collect();
+    entries.sort_by(|a, b| newest_first(a, b));
+    entries
+        .into_iter()
+        .flat_map(move |entry| {
+            entry
+                .content
+                .lines()
+                .enumerate()
+                .filter(move |(_, l)| {
+                    let lower = l.to_lowercase();
+                    terms.iter().all(|t| lower.contains(t.as_str()))
+                        && !(entry.truncated && is_truncation_marker(l))
+                })
+                .map(move |(i, line)| SearchHit {
+                    project: &entry.project,
+                    path: &entry.path,
+                    line_no: (!entry.truncated).then_some(i + 1),
+                    line,
+                })
+        })
+        .collect()
+}
+
+/// Split a query into lowercase terms that must all match. Double-quoted text
+/// stays one phrase (an unclosed quote runs to the end); unquoted stop words
+/// are dropped unless the query has nothing else.
+fn query_terms(query: &str) -> Vec<String> {
+    let mut phrases: Vec<String> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    for (i, chunk) in query.split('"').enumerate() {
+        if i % 2 == 1 {
+            let phrase = chunk.trim().to_lowercase();
+            if !phrase.is_empty() {
+                phrases.push(phrase);
+            }
+        } else {
+            words.extend(chunk.split_whitespace().map(str::to_lowercase));
         }
-    };
-    match serde_json::from_str(&raw) {
-        Ok(entries) => entries,
-        Err(e) => {
-            eprintln!("mem: index at {} is corrupt ({e})", path.display());
-            eprintln!("mem: run `mem index` to rebuild it");
-            Vec::new()
+    }
+    let content_words: Vec<String> = words
+        .iter()
+        .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+        .cloned()
+        .collect();
+    if !content_words.is_empty() || !phrases.is_empty() {
+        words = content_words;
+    }
+    phrases.extend(words);
+    phrases
+}
+
+/// Rewrite `query` with each single-word term that appears nowhere in the
+/// index replaced by the closest indexed word, or `None` if nothing changes.
+fn suggest_query(index: &[IndexEntry], query: &str) -> Option<String> {
+    let vocabulary: std::collections::BTreeSet<String> = index
+        .iter()
+        .flat_map(|e| {
+            e.content
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        })
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut changed = false;
+    let terms: Vec<String> = query_terms(query)
+        .into_iter()
+        .map(|term| {
+            if term.contains(' ') || vocabulary.iter().any(|w| w.contains(term.as_str())) {
+                return term;
+            }
+            let max_distance = (term.chars().count() / 3).max(1);
+            let closest = vocabulary
+                .iter()
+                .map(|w| (edit_distance(&term, w), w))
+                .filter(|(d, _)| *d <= max_distance)
+                .min_by_key(|(d, _)| *d);
+            match closest {
+                Some((_, word)) => {
+                    changed = true;
+                    word.clone()
+                }
+                None => term,
+            }
+        })
+        .collect();
+
+    changed.then(|| {
+        terms
+            .iter()
+            .map(|t| {
+                if t.contains(' ') {
+                    format!("\"{t}\"")
+                } else {
+                    t.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
         }
+        prev = cur;
     }
+    prev[b.len()]
 }
 
-fn save_index(entries: &[IndexEntry]) -> Result<()> {
-    let path = index_path().context("$HOME not set")?;
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+// ── export ────────────────────────────────────────────────────────────────────
+
+fn cmd_export(
+    format: ExportFormat,
+    project: Option<String>,
+    out: Option<PathBuf>,
+    index_path: Option<&Path>,
+    screen_reader: bool,
+) -> Result<()> {
+    let index = load_index(index_path);
+    let mut entries: Vec<&IndexEntry> = index
+        .iter()
+        .filter(|e| match project.as_deref() {
+            Some(p) => e.project.contains(p),
+            None => true,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.project.cmp(&b.project).then_with(|| a.path.cmp(&b.path)));
+
+    if entries.is_empty() {
+        anyhow::bail!("nothing to export — run `mem index` first or check --project");
     }
-    let tmp = path.with_extension("json.tmp");
-    std::fs::write(&tmp, serde_json::to_string(entries)?)
-        .with_context(|| format!("write {}", tmp.display()))?;
-    if let Err(e) = std::fs::rename(&tmp, &path) {
-        let _ = std::fs::remove_file(&tmp);
-        return Err(e).with_context(|| format!("rename to {}", path.display()));
+    let ownership = load_config().ownership;
+    // The index keeps only an excerpt of oversized files; export the source.
+    let full: Vec<IndexEntry> = entries
+        .iter()
+        .map(|e| full_entry(e))
+        .collect::<Result<_>>()?;
+    let entries: Vec<&IndexEntry> = full.iter().collect();
+
+    let Some(dir) = out else {
+        print!("{}", render_export(&entries, format, &ownership)?);
+        return Ok(());
+    };
+    std::fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    for entry in &entries {
+        let path = dir.join(format!("{}.{}", entry.project, format.extension()));
+        std::fs::write(&path, render_export(&[entry], format, &ownership)?)
+            .with_context(|| format!("write {}", path.display()))?;
     }
+    let manifest = export_manifest(&entries, format, &ownership, unix_now());
+    let path = dir.join(EXPORT_MANIFEST);
+    std::fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")
+        .with_context(|| format!("write {}", path.display()))?;
+    println!(
+        "{}",
+        for_reader(
+            i18n::fill(Msg::ExportedTo, &[&entries.len(), &dir.display()]),
+            screen_reader
+        )
+    );
     Ok(())
 }
 
-// ── helpers ───────────────────────────────────────────────────────────────────
-
-fn resolve_cwd(project_override: Option<PathBuf>) -> Result<PathBuf> {
-    if let Some(p) = project_override {
-        return Ok(p);
+/// `entry` with its whole file content when the index holds only an excerpt.
+/// Refuses rather than export a partial copy that `mem import` would write back.
+fn full_entry(entry: &IndexEntry) -> Result<IndexEntry> {
+    if !entry.truncated {
+        return Ok(entry.clone());
     }
-    if std::io::stdin().is_terminal() {
-        return Ok(std::env::current_dir()?);
+    let content = std::fs::read_to_string(&entry.path).with_context(|| {
+        format!(
+            "{} is truncated in the index and the file cannot be read; \
+             not exporting a partial copy",
+            entry.path
+        )
+    })?;
+    Ok(IndexEntry {
+        lines: Some(content.lines().count()),
+        content,
+        mtime: file_mtime(Path::new(&entry.path)),
+        truncated: false,
+        ..entry.clone()
+    })
+}
+
+/// Who owns the exported content and which files the bundle contains.
+fn export_manifest<'a>(
+    entries: &[&'a IndexEntry],
+    format: ExportFormat,
+    ownership: &'a Ownership,
+    now: i64,
+) -> ExportManifest<'a> {
+    ExportManifest {
+        generated_at: now,
+        mem_version: env!("CARGO_PKG_VERSION"),
+        ownership,
+        files: entries
+            .iter()
+            .map(|e| ManifestFile {
+                project: &e.project,
+                file: format!("{}.{}", e.project, format.extension()),
+                source_path: &e.path,
+                modified_at: e.mtime,
+                lines: e.line_count(),
+            })
+            .collect(),
     }
-    let mut buf = String::new();
-    std::io::stdin().read_to_string(&mut buf)?;
-    match serde_json::from_str::<HookStdin>(&buf) {
-        Ok(hook) => Ok(hook
-            .cwd
-            .map(PathBuf::from)
-            .unwrap_or(std::env::current_dir()?)),
-        Err(e) => {
-            eprintln!(
-                "mem: session-start received unexpected stdin ({e}); \
-                 falling back to current directory. Payload: {:?}",
-                &buf[..buf.len().min(200)]
-            );
-            Ok(std::env::current_dir()?)
+}
+
+/// A JSON array, or Markdown with one `##` section per project. Ownership
+/// fields are stamped on every entry when configured.
+fn render_export(
+    entries: &[&IndexEntry],
+    format: ExportFormat,
+    ownership: &Ownership,
+) -> Result<String> {
+    match format {
+        ExportFormat::Json => {
+            let records: Vec<ExportEntry> = entries
+                .iter()
+                .map(|e| ExportEntry {
+                    project: &e.project,
+                    source_path: &e.path,
+                    modified_at: e.mtime,
+                    content: &e.content,
+                    ownership,
+                })
+                .collect();
+            Ok(serde_json::to_string_pretty(&records)? + "\n")
         }
+        ExportFormat::Markdown => Ok(entries
+            .iter()
+            .map(|e| {
+                let attribution = ownership
+                    .attribution()
+                    .map(|a| format!("_{a}_\n\n"))
+                    .unwrap_or_default();
+                format!(
+                    "## {}\n\n_Source: `{}`_\n\n{attribution}{}\n",
+                    e.project,
+                    e.path,
+                    e.content.trim()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")),
     }
 }
 
-fn find_memory_md(cwd: &Path) -> Option<(String, PathBuf)> {
-    // Strategy 1: git repo root
-    if let Some(root) = git_repo_root(cwd) {
+// ── import ────────────────────────────────────────────────────────────────────
+
+fn cmd_import(
+    path: PathBuf,
+    remap: Vec<String>,
+    overwrite: bool,
+    screen_reader: bool,
+) -> Result<()> {
+    let remap: Vec<(String, String)> = remap
+        .iter()
+        .map(|r| {
+            r.split_once('=')
+                .map(|(old, new)| (old.to_string(), new.to_string()))
+                .with_context(|| format!("--remap expects OLD=NEW, got `{r}`"))
+        })
+        .collect::<Result<_>>()?;
+
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&path)
+            .with_context(|| format!("read {}", path.display()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .filter(|p| !p.ends_with(EXPORT_MANIFEST))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path]
+    };
+
+    let mut records: Vec<ImportEntry> = Vec::new();
+    for file in &files {
+        let raw =
+            std::fs::read_to_string(file).with_context(|| format!("read {}", file.display()))?;
+        let parsed: Vec<ImportEntry> = serde_json::from_str(&raw).with_context(|| {
+            format!(
+                "{} is not a JSON export — use `mem export --format json`",
+                file.display()
+            )
+        })?;
+        records.extend(parsed);
+    }
+    if records.is_empty() {
+        anyhow::bail!("nothing to import");
+    }
+
+    let projects_dir = dirs::home_dir()
+        .context("$HOME not set")?
+        .join(".claude")
+        .join("projects");
+    let stats = import_entries(&records, &remap, overwrite, &projects_dir)?;
+    println!(
+        "{}",
+        for_reader(
+            i18n::fill(
+                Msg::ImportSummary,
+                &[&stats.new, &stats.updated, &stats.skipped, &stats.rejected]
+            ),
+            screen_reader
+        )
+    );
+    if stats.new + stats.updated > 0 {
+        println!("{}", i18n::text(Msg::ImportRunIndex));
+    }
+    Ok(())
+}
+
+/// Write each record's content to its (remapped) source path. Files that
+/// already hold the same content are skipped, as are differing files unless
+/// `overwrite` is set. Targets failing `import_target` are rejected.
+fn import_entries(
+    records: &[ImportEntry],
+    remap: &[(String, String)],
+    overwrite: bool,
+    projects_dir: &Path,
+) -> Result<ImportStats> {
+    let mut stats = ImportStats::default();
+    for record in records {
+        if record.content.lines().any(is_truncation_marker) {
+            eprintln!(
+                "mem: rejected {} — archived content is a truncated excerpt",
+                record.source_path
+            );
+            stats.rejected += 1;
+            continue;
+        }
+        let target = match import_target(&record.source_path, remap, projects_dir) {
+            Ok(t) => t,
+            Err(reason) => {
+                eprintln!("mem: rejected {} — {reason}", record.source_path);
+                stats.rejected += 1;
+                continue;
+            }
+        };
+
+        let existing = match std::fs::read_to_string(&target) {
+            Ok(c) => Some(c),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).with_context(|| format!("read {}", target.display())),
+        };
+        match existing {
+            Some(c) if c == record.content => {
+                stats.skipped += 1;
+                continue;
+            }
+            Some(_) if !overwrite => {
+                eprintln!(
+                    "mem: {} differs from the archive ({}) — skipped, use --overwrite",
+                    target.display(),
+                    record.project
+                );
+                stats.skipped += 1;
+                continue;
+            }
+            Some(_) => stats.updated += 1,
+            None => stats.new += 1,
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create {}", parent.display()))?;
+        }
+        // Temp file + rename: an interrupted import never leaves a partial MEMORY.md.
+        let tmp = target.with_extension("md.tmp");
+        std::fs::write(&tmp, &record.content)
+            .with_context(|| format!("write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &target)
+            .with_context(|| format!("rename to {}", target.display()))?;
+    }
+    Ok(stats)
+}
+
+/// Where an archived `source_path` may be written. Archives are shared, so a
+/// record may only name a `MEMORY.md` under `projects_dir/<project>/memory/`
+/// unless the user remapped it explicitly; relative paths and `..` are refused
+/// either way.
+fn import_target(
+    source_path: &str,
+    remap: &[(String, String)],
+    projects_dir: &Path,
+) -> Result<PathBuf, &'static str> {
+    let source = Path::new(source_path);
+    let remapped = remap.iter().find_map(|(old, new)| {
+        source
+            .strip_prefix(old)
+            .ok()
+            .map(|rest| Path::new(new).join(rest))
+    });
+    let explicit = remapped.is_some();
+    let target = remapped.unwrap_or_else(|| source.to_path_buf());
+
+    if !target.is_absolute() {
+        return Err("not an absolute path");
+    }
+    if target
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err("path contains `..`");
+    }
+    if target.file_name() != Some(std::ffi::OsStr::new("MEMORY.md")) {
+        return Err("not a MEMORY.md file");
+    }
+    if !explicit {
+        let inside = target
+            .strip_prefix(projects_dir)
+            .ok()
+            .map(|rest| rest.components().collect::<Vec<_>>())
+            .is_some_and(|parts| {
+                parts.len() == 3
+                    && matches!(parts[0], std::path::Component::Normal(_))
+                    && parts[1].as_os_str() == "memory"
+            });
+        if !inside {
+            return Err("outside ~/.claude/projects/*/memory/ — use --remap to place it elsewhere");
+        }
+    }
+    Ok(target)
+}
+
+// ── schedule ──────────────────────────────────────────────────────────────────
+
+fn cmd_schedule(action: ScheduleAction, index: Option<&Path>, screen_reader: bool) -> Result<()> {
+    let crontab = read_crontab()?;
+    let say = |text: String| println!("{}", for_reader(text, screen_reader));
+    match action {
+        ScheduleAction::Install => {
+            let exe = std::env::current_exe().context("cannot locate the mem binary")?;
+            let index = index.context("$HOME not set")?;
+            let log = schedule_log_path().context("$HOME not set")?;
+            if let Some(parent) = log.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("create {}", parent.display()))?;
+            }
+            let job = format!(
+                "{SCHEDULE_WHEN} {} --index {} index >> {} 2>&1 {SCHEDULE_MARKER}",
+                cron_quote(&exe.display().to_string()),
+                cron_quote(&index.display().to_string()),
+                cron_quote(&log.display().to_string()),
+            );
+            write_crontab(&with_scheduled_job(&crontab, &job))?;
+            say(i18n::text(Msg::ScheduleInstalled).to_string());
+            say(i18n::fill(Msg::ScheduleLog, &[&log.display()]));
+        }
+        ScheduleAction::Status => match scheduled_job(&crontab) {
+            Some(job) => {
+                say(i18n::fill(Msg::ScheduleEntry, &[&job]));
+                let last_run = schedule_log_path()
+                    .filter(|p| p.exists())
+                    .map(|p| file_mtime(&p));
+                let when = match last_run {
+                    Some(t) => i18n::fill(Msg::Ago, &[&format_age(unix_now() - t)]),
+                    None => i18n::text(Msg::ScheduleNever).to_string(),
+                };
+                say(i18n::fill(Msg::ScheduleLastRun, &[&when]));
+            }
+            None => say(i18n::text(Msg::ScheduleNone).to_string()),
+        },
+        ScheduleAction::Remove => {
+            if scheduled_job(&crontab).is_none() {
+                say(i18n::text(Msg::ScheduleNothingToRemove).to_string());
+                return Ok(());
+            }
+            write_crontab(&without_scheduled_job(&crontab))?;
+            say(i18n::text(Msg::ScheduleRemoved).to_string());
+        }
+    }
+    Ok(())
+}
+
+fn schedule_log_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".mem").join("logs").join("index.log"))
+}
+
+/// Current user crontab; a user with no crontab yet reads as empty.
+fn read_crontab() -> Result<String> {
+    let out = Command::new("crontab")
+        .arg("-l")
+        .stdin(Stdio::null())
+        .output()
+        .context("crontab not found — mem schedule needs cron")?;
+    if out.status.success() {
+        return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    if stderr.contains("no crontab") {
+        return Ok(String::new());
+    }
+    anyhow::bail!("crontab -l failed: {}", stderr.trim())
+}
+
+fn write_crontab(content: &str) -> Result<()> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("crontab not found — mem schedule needs cron")?;
+    child
+        .stdin
+        .take()
+        .context("crontab stdin")?
+        .write_all(content.as_bytes())
+        .context("write crontab")?;
+    if !child.wait()?.success() {
+        anyhow::bail!("crontab rejected the new entry");
+    }
+    Ok(())
+}
+
+fn scheduled_job(crontab: &str) -> Option<&str> {
+    crontab.lines().find(|l| l.ends_with(SCHEDULE_MARKER))
+}
+
+fn without_scheduled_job(crontab: &str) -> String {
+    crontab
+        .lines()
+        .filter(|l| !l.ends_with(SCHEDULE_MARKER))
+        .map(|l| format!("{l}\n"))
+        .collect()
+}
+
+/// Replace any previous managed line so reinstalling never duplicates it.
+fn with_scheduled_job(crontab: &str, job: &str) -> String {
+    without_scheduled_job(crontab) + job + "\n"
+}
+
+/// Single-quote `s` for the shell, escaping `%` which cron would otherwise
+/// turn into a newline.
+fn cron_quote(s: &str) -> String {
+    shell_quote(&s.replace('%', r"\%"))
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+// ── index persistence ─────────────────────────────────────────────────────────
+
+/// `--index` wins over `MEM_INDEX_PATH`, which wins over `~/.mem/index.json`.
+fn resolve_index_path(
+    flag: Option<PathBuf>,
+    env: Option<std::ffi::OsString>,
+    home: Option<PathBuf>,
+) -> Option<PathBuf> {
+    flag.or_else(|| env.filter(|v| !v.is_empty()).map(PathBuf::from))
+        .or_else(|| home.map(|h| h.join(".mem").join("index.json")))
+}
+
+/// `path` relative to the current directory; unchanged if that is unknown.
+fn absolute(path: PathBuf) -> PathBuf {
+    std::env::current_dir()
+        .map(|dir| dir.join(&path))
+        .unwrap_or(path)
+}
+
+fn load_index(path: Option<&Path>) -> Vec<IndexEntry> {
+    let Some(path) = path else {
+        return Vec::new();
+    };
+    let raw = match std::fs::read_to_string(path) {
+        Ok(r) => r,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            eprintln!("mem: cannot read index {}: {e}", path.display());
+            eprintln!("mem: run `mem index` to rebuild, or check file permissions");
+            return Vec::new();
+        }
+    };
+    match serde_json::from_str(&raw) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("mem: index at {} is corrupt ({e})", path.display());
+            eprintln!("mem: run `mem index` to rebuild it");
+            Vec::new()
+        }
+    }
+}
+
+fn save_index(path: Option<&Path>, entries: &[IndexEntry]) -> Result<()> {
+    let path = path.context("$HOME not set")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string(entries)?)
+        .with_context(|| format!("write {}", tmp.display()))?;
+    if let Err(e) = std::fs::rename(&tmp, path) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e).with_context(|| format!("rename to {}", path.display()));
+    }
+    Ok(())
+}
+
+// ── config ────────────────────────────────────────────────────────────────────
+
+fn config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".mem").join("config.json"))
+}
+
+fn load_config() -> Config {
+    let Some(path) = config_path() else {
+        return Config::default();
+    };
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(r) => r,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Config::default(),
+        Err(e) => {
+            eprintln!("mem: cannot read config {}: {e}", path.display());
+            return Config::default();
+        }
+    };
+    match serde_json::from_str(&raw) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!(
+                "mem: config at {} is invalid ({e}); using defaults",
+                path.display()
+            );
+            Config::default()
+        }
+    }
+}
+
+// ── hook schema ───────────────────────────────────────────────────────────────
+
+fn hook_schema_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".mem").join("hook_schema.json"))
+}
+
+fn load_hook_schema() -> Option<HookSchema> {
+    let raw = std::fs::read_to_string(hook_schema_path()?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Compare a payload's fields with the last recorded schema. Returns the new
+/// record and whether unknown fields should be logged now (at most once a day).
+fn observe_hook_fields(prev: &HookSchema, fields: Vec<String>, today: i64) -> (HookSchema, bool) {
+    let unknown: Vec<String> = fields
+        .iter()
+        .filter(|f| !KNOWN_HOOK_FIELDS.contains(&f.as_str()))
+        .cloned()
+        .collect();
+    let warn = !unknown.is_empty() && (prev.warned_day != today || prev.unknown != unknown);
+    let next = HookSchema {
+        fields,
+        unknown,
+        warned_day: if warn { today } else { prev.warned_day },
+    };
+    (next, warn)
+}
+
+/// Record the payload shape and log unknown fields once a day. Never fails the
+/// hook — a broken schema record only costs the drift report.
+fn record_hook_schema(fields: Vec<String>) {
+    let prev = load_hook_schema().unwrap_or_default();
+    let (next, warn) = observe_hook_fields(&prev, fields, unix_now() / 86_400);
+    if warn {
+        eprintln!(
+            "mem: hook payload has unknown field(s): {} — Claude Code may have changed its protocol",
+            next.unknown.join(", ")
+        );
+    }
+    if next != prev {
+        if let Err(e) = save_hook_schema(&next) {
+            eprintln!("mem: cannot record hook schema: {e:#}");
+        }
+    }
+}
+
+fn save_hook_schema(schema: &HookSchema) -> Result<()> {
+    let path = hook_schema_path().context("$HOME not set")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    atomic_write_json(&path, &serde_json::to_value(schema)?)
+}
+
+fn hook_schema_status(schema: Option<&HookSchema>, lang: Lang) -> String {
+    match schema {
+        None => i18n::text_in(lang, Msg::PayloadNone).to_string(),
+        Some(s) if s.unknown.is_empty() => i18n::text_in(lang, Msg::PayloadKnown).to_string(),
+        Some(s) => i18n::fill_in(
+            lang,
+            Msg::PayloadDrift,
+            &[&s.unknown.len(), &s.unknown.join(", ")],
+        ),
+    }
+}
+
+// ── hook runs ─────────────────────────────────────────────────────────────────
+
+fn hook_runs_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".mem").join("hook_runs.json"))
+}
+
+fn load_hook_runs() -> Vec<HookRun> {
+    let Some(path) = hook_runs_path() else {
+        return Vec::new();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|e| {
+            eprintln!(
+                "mem: hook run log {} is corrupt ({e}); starting over",
+                path.display()
+            );
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Append a run to the hook log, keeping the newest `HOOK_RUNS_KEPT`. Called
+/// on success and failure alike; a logging failure never changes the result.
+fn record_hook_run(hook: &str, elapsed: std::time::Duration, result: &Result<()>) {
+    let mut runs = load_hook_runs();
+    push_hook_run(
+        &mut runs,
+        HookRun {
+            hook: hook.to_string(),
+            started_at: unix_now() - elapsed.as_secs() as i64,
+            duration_ms: elapsed.as_millis() as u64,
+            exit_code: if result.is_ok() { 0 } else { 1 },
+            error: result.as_ref().err().map(|e| format!("{e:#}")),
+        },
+    );
+    if let Err(e) = save_hook_runs(&runs) {
+        eprintln!("mem: cannot record hook run: {e:#}");
+    }
+}
+
+fn push_hook_run(runs: &mut Vec<HookRun>, run: HookRun) {
+    runs.push(run);
+    if runs.len() > HOOK_RUNS_KEPT {
+        runs.drain(..runs.len() - HOOK_RUNS_KEPT);
+    }
+}
+
+fn save_hook_runs(runs: &[HookRun]) -> Result<()> {
+    let path = hook_runs_path().context("$HOME not set")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    atomic_write_json(&path, &serde_json::to_value(runs)?)
+}
+
+// ── truncation ────────────────────────────────────────────────────────────────
+
+/// Cap `content` at `max_lines` lines and `max_bytes` bytes, keeping the head
+/// and tail (where the title and the most recent entries usually are) around
+/// a truncation marker, followed by the Markdown headings of the cut middle
+/// as an outline when they fit. The marker counts against both caps. A limit
+/// of 0 disables that cap.
+fn truncate_head_tail(content: &str, max_lines: usize, max_bytes: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let max_lines = if max_lines == 0 {
+        usize::MAX
+    } else {
+        max_lines
+    };
+    let max_bytes = if max_bytes == 0 {
+        usize::MAX
+    } else {
+        max_bytes
+    };
+    if lines.len() <= max_lines && content.len() <= max_bytes {
+        return content.to_string();
+    }
+
+    // Prefix sums keep each candidate split O(1) on very large files.
+    let n = lines.len();
+    let mut line_bytes = vec![0usize; n + 1];
+    let mut heading_bytes = vec![0usize; n + 1];
+    let mut heading_count = vec![0usize; n + 1];
+    for (i, line) in lines.iter().enumerate() {
+        let is_heading = usize::from(line.starts_with('#'));
+        line_bytes[i + 1] = line_bytes[i] + line.len() + 1;
+        heading_bytes[i + 1] = heading_bytes[i] + is_heading * (line.len() + 1);
+        heading_count[i + 1] = heading_count[i] + is_heading;
+    }
+
+    // Prefer an outline of the cut headings; give it up before head or tail.
+    // One line of `max_lines` is reserved for the marker.
+    for with_headings in [true, false] {
+        for keep in (0..=n.min(max_lines - 1)).rev() {
+            let head = keep.div_ceil(2);
+            let tail = keep - head;
+            let (outline, outline_bytes) = if with_headings {
+                (
+                    heading_count[n - tail] - heading_count[head],
+                    heading_bytes[n - tail] - heading_bytes[head],
+                )
+            } else {
+                (0, 0)
+            };
+            let marker = truncation_marker(n - keep - outline);
+            let bytes = line_bytes[head] + outline_bytes + (marker.len() + 1) + line_bytes[n]
+                - line_bytes[n - tail];
+            let fits = keep + outline < max_lines && bytes <= max_bytes;
+            // With nothing left to drop, the bare marker is the floor.
+            if (fits && keep > 0) || (keep == 0 && !with_headings) {
+                let mut out: Vec<&str> = lines[..head].to_vec();
+                out.push(&marker);
+                if with_headings {
+                    out.extend(lines[head..n - tail].iter().filter(|l| l.starts_with('#')));
+                }
+                out.extend_from_slice(&lines[n - tail..]);
+                return out.join("\n");
+            }
+        }
+    }
+    unreachable!("keep = 0 without headings is always returned")
+}
+
+fn truncation_marker(lines: usize) -> String {
+    format!("[truncated {lines} lines — run `mem show-file`]")
+}
+
+fn is_truncation_marker(line: &str) -> bool {
+    line.starts_with("[truncated ") && line.ends_with(" lines — run `mem show-file`]")
+}
+
+// ── helpers ───────────────────────────────────────────────────────────────────
+
+fn resolve_cwd(project_override: Option<PathBuf>) -> Result<PathBuf> {
+    if let Some(p) = project_override {
+        return Ok(p);
+    }
+    if std::io::stdin().is_terminal() {
+        return Ok(std::env::current_dir()?);
+    }
+    let mut buf = String::new();
+    std::io::stdin().read_to_string(&mut buf)?;
+    match parse_hook_stdin(&buf) {
+        Ok((hook, fields)) => {
+            record_hook_schema(fields);
+            Ok(hook
+                .cwd
+                .map(PathBuf::from)
+                .unwrap_or(std::env::current_dir()?))
+        }
+        Err(e) => {
+            eprintln!(
+                "mem: session-start received unexpected stdin ({e}); \
+                 falling back to current directory. Payload: {:?}",
+                &buf[..buf.len().min(200)]
+            );
+            Ok(std::env::current_dir()?)
+        }
+    }
+}
+
+/// Parse a hook payload, rejecting non-objects and a mistyped `cwd` instead of
+/// silently defaulting them. Only `cwd` is type-checked — it is the one field
+/// mem uses; other known fields are recorded by name only. Also returns the
+/// sorted field names.
+fn parse_hook_stdin(raw: &str) -> Result<(HookStdin, Vec<String>)> {
+    let value: serde_json::Value = serde_json::from_str(raw)?;
+    let mut fields: Vec<String> = value
+        .as_object()
+        .context("hook payload is not a JSON object")?
+        .keys()
+        .cloned()
+        .collect();
+    fields.sort();
+    let hook = serde_json::from_value(value)?;
+    Ok((hook, fields))
+}
+
+fn find_memory_md(cwd: &Path) -> Option<(String, PathBuf)> {
+    find_memory_md_in(cwd, git_repo_root(cwd).as_deref())
+}
+
+/// `find_memory_md` with the git root already resolved by the caller.
+fn find_memory_md_in(cwd: &Path, repo_root: Option<&str>) -> Option<(String, PathBuf)> {
+    // Strategy 1: git repo root
+    if let Some(root) = repo_root {
         let path = PathBuf::from(&root).join("MEMORY.md");
         if path.exists() {
             match std::fs::read_to_string(&path) {
@@ -517,6 +2059,18 @@ fn file_mtime(path: &Path) -> i64 {
         .unwrap_or(0)
 }
 
+/// Order index entries most recently modified first, ties broken by path.
+fn newest_first(a: &IndexEntry, b: &IndexEntry) -> std::cmp::Ordering {
+    b.mtime.cmp(&a.mtime).then_with(|| a.path.cmp(&b.path))
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
 fn hook_command_exists(entry: &serde_json::Value, cmd: &str) -> bool {
     session_start_commands(entry).any(|c| c == cmd)
 }
@@ -536,6 +2090,44 @@ fn session_start_commands<'a>(entry: &'a serde_json::Value) -> impl Iterator<Ite
         .filter_map(|h| h.get("command").and_then(|c| c.as_str()))
 }
 
+/// Pipe `text` into the platform clipboard tool. Shelling out keeps the binary
+/// free of windowing-system dependencies, the same way git is invoked.
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    copy_with(text, CLIPBOARD_COMMANDS)?;
+    eprintln!("mem: copied to clipboard");
+    Ok(())
+}
+
+/// Try each command in order and return the first that accepted `text`. A tool
+/// that is missing, closes its input early (e.g. `wl-copy` outside Wayland)
+/// or exits non-zero falls through to the next one.
+fn copy_with<'a>(text: &str, commands: &[(&'a str, &[&str])]) -> Result<&'a str> {
+    for (cmd, args) in commands {
+        let Ok(mut child) = Command::new(cmd)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue; // not installed
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        if !written {
+            let _ = child.kill();
+            let _ = child.wait();
+            continue;
+        }
+        if child.wait().is_ok_and(|status| status.success()) {
+            return Ok(cmd);
+        }
+    }
+    anyhow::bail!("no clipboard tool found — install pbcopy, wl-copy, xclip or xsel")
+}
+
 fn atomic_write_json(path: &Path, value: &serde_json::Value) -> Result<()> {
     let tmp = path.with_extension("json.tmp");
     std::fs::write(&tmp, serde_json::to_string_pretty(value)? + "\n")
@@ -546,10 +2138,10 @@ fn atomic_write_json(path: &Path, value: &serde_json::Value) -> Result<()> {
 
 fn check_session_start_hook(settings_path: &Path) -> &'static str {
     let Ok(raw) = std::fs::read_to_string(settings_path) else {
-        return "NOT installed — run `mem init`";
+        return i18n::text(Msg::NotInstalled);
     };
     let Ok(val) = serde_json::from_str::<serde_json::Value>(&raw) else {
-        return "malformed settings.json";
+        return i18n::text(Msg::MalformedSettings);
     };
     let entry = val
         .get("hooks")
@@ -559,9 +2151,9 @@ fn check_session_start_hook(settings_path: &Path) -> &'static str {
     // Accept any command ending with " session-start" to handle path changes after reinstall.
     let has_hook = session_start_commands(&entry).any(|c| c.ends_with(" session-start"));
     if has_hook {
-        "installed"
+        i18n::text(Msg::Installed)
     } else {
-        "NOT installed — run `mem init`"
+        i18n::text(Msg::NotInstalled)
     }
 }
 
@@ -621,19 +2213,34 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let path = tmp.path().join("settings.json");
         std::fs::write(&path, "{}").unwrap();
-        wire_session_start_hook(&path).unwrap();
-        wire_session_start_hook(&path).unwrap();
+        wire_session_start_hook(&path, None).unwrap();
+        wire_session_start_hook(&path, None).unwrap();
         let val: serde_json::Value =
             serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
         assert_eq!(val["hooks"]["SessionStart"].as_array().unwrap().len(), 1);
     }
 
+    #[test]
+    fn wire_session_start_hook_passes_index_and_replaces_old_hook() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("settings.json");
+        std::fs::write(&path, "{}").unwrap();
+        wire_session_start_hook(&path, None).unwrap();
+        assert!(wire_session_start_hook(&path, Some(Path::new("/data/it's.json"))).unwrap());
+        let val: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let groups = val["hooks"]["SessionStart"].as_array().unwrap();
+        assert_eq!(groups.len(), 1);
+        let cmd = groups[0]["hooks"][0]["command"].as_str().unwrap();
+        assert!(cmd.ends_with(r" --index '/data/it'\''s.json' session-start"));
+    }
+
     #[test]
     fn wire_session_start_hook_preserves_existing_keys() {
         let tmp = tempfile::tempdir().unwrap();
         let path = tmp.path().join("settings.json");
         std::fs::write(&path, r#"{"model":"claude-sonnet-4-6"}"#).unwrap();
-        wire_session_start_hook(&path).unwrap();
+        wire_session_start_hook(&path, None).unwrap();
         let val: serde_json::Value =
             serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
         assert_eq!(val["model"].as_str(), Some("claude-sonnet-4-6"));
@@ -679,6 +2286,9 @@ mod tests {
             path: tmp.path().join("MEMORY.md").to_string_lossy().to_string(),
             content: "- Used JWT for auth".to_string(),
             mtime: 12345,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
         };
 
         // Serialize and reload
@@ -712,20 +2322,826 @@ mod tests {
         assert!(parsed.cwd.is_none());
     }
 
+    #[test]
+    fn parse_hook_stdin_returns_sorted_fields() {
+        let (hook, fields) =
+            parse_hook_stdin(r#"{"session_id":"abc","cwd":"/tmp/p","model":"x"}"#).unwrap();
+        assert_eq!(hook.cwd.as_deref(), Some("/tmp/p"));
+        assert_eq!(fields, vec!["cwd", "model", "session_id"]);
+    }
+
+    #[test]
+    fn parse_hook_stdin_rejects_non_object_and_mistyped_cwd() {
+        assert!(parse_hook_stdin("[1,2]").is_err());
+        assert!(parse_hook_stdin(r#"{"cwd":42}"#).is_err());
+    }
+
+    #[test]
+    fn observe_hook_fields_warns_once_per_day() {
+        let fields = vec!["cwd".to_string(), "model".to_string()];
+        let (first, warn) = observe_hook_fields(&HookSchema::default(), fields.clone(), 100);
+        assert!(warn);
+        assert_eq!(first.unknown, vec!["model"]);
+        assert_eq!(first.warned_day, 100);
+
+        let (same_day, warn) = observe_hook_fields(&first, fields.clone(), 100);
+        assert!(!warn);
+        assert_eq!(same_day, first);
+
+        let (_, warn) = observe_hook_fields(&first, fields, 101);
+        assert!(warn);
+    }
+
+    #[test]
+    fn observe_hook_fields_known_payload_is_quiet() {
+        let fields = vec!["cwd".to_string(), "session_id".to_string()];
+        let (schema, warn) = observe_hook_fields(&HookSchema::default(), fields, 1);
+        assert!(!warn);
+        assert!(schema.unknown.is_empty());
+        assert_eq!(
+            hook_schema_status(Some(&schema), Lang::En),
+            "matches known fields"
+        );
+    }
+
     #[test]
     fn search_matches_lines_case_insensitive() {
-        let entries = vec![IndexEntry {
+        let entries = [IndexEntry {
             project: "proj".to_string(),
             path: "/proj/MEMORY.md".to_string(),
             content: "- Used JWT for auth\n- Rejected OAuth (too complex)".to_string(),
             mtime: 0,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
         }];
-        let query = "jwt";
-        let matches: Vec<&str> = entries[0]
-            .content
-            .lines()
-            .filter(|l| l.to_lowercase().contains(query))
-            .collect();
-        assert_eq!(matches, vec!["- Used JWT for auth"]);
+        let hits = search_index(&entries, "jwt");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line, "- Used JWT for auth");
+    }
+
+    #[test]
+    fn search_requires_every_term_on_the_line() {
+        let entries = [IndexEntry {
+            project: "proj".to_string(),
+            path: "/proj/MEMORY.md".to_string(),
+            content:
+                "- JWT refresh token lives in an httpOnly cookie\n- JWT signing key rotates monthly"
+                    .to_string(),
+            mtime: 0,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        }];
+        let hits = search_index(&entries, "jwt refresh cookie");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line_no, Some(1));
+        assert_eq!(search_index(&entries, "jwt").len(), 2);
+        assert!(search_index(&entries, "   ").is_empty());
+    }
+
+    #[test]
+    fn search_orders_newest_file_first_regardless_of_index_order() {
+        let entry = |path: &str, mtime: i64| IndexEntry {
+            project: path.to_string(),
+            path: path.to_string(),
+            content: "- jwt".to_string(),
+            mtime,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        };
+        let index = [
+            entry("/b", 10),
+            entry("/old", 1),
+            entry("/a", 10),
+            entry("/new", 20),
+        ];
+        let mut reversed = index.iter().rev().cloned().collect::<Vec<_>>();
+        reversed.swap(0, 2);
+        for entries in [&index[..], &reversed[..]] {
+            let paths: Vec<&str> = search_index(entries, "jwt")
+                .iter()
+                .map(|h| h.path)
+                .collect();
+            assert_eq!(paths, vec!["/new", "/a", "/b", "/old"]);
+        }
+    }
+
+    #[test]
+    fn search_terms_match_partial_identifiers() {
+        let entries = [IndexEntry {
+            project: "proj".to_string(),
+            path: "/proj/MEMORY.md".to_string(),
+            content: "- search_unified merges memory and file hits".to_string(),
+            mtime: 0,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        }];
+        assert_eq!(search_index(&entries, "search_uni").len(), 1);
+        assert_eq!(search_index(&entries, "unif").len(), 1);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("jwt", "jwt"), 0);
+        assert_eq!(edit_distance("jtw", "jwt"), 2);
+        assert_eq!(edit_distance("stripe", "strip"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+    }
+
+    #[test]
+    fn suggest_query_fixes_typos_only() {
+        let entries = [IndexEntry {
+            project: "proj".to_string(),
+            path: "/proj/MEMORY.md".to_string(),
+            content: "- Payment webhooks must be idempotent".to_string(),
+            mtime: 0,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        }];
+        assert_eq!(
+            suggest_query(&entries, "webhoks idempotent").as_deref(),
+            Some("webhooks idempotent")
+        );
+        assert_eq!(suggest_query(&entries, "webhooks"), None);
+        assert_eq!(suggest_query(&entries, "kubernetes"), None);
+    }
+
+    #[test]
+    fn query_terms_handles_quotes_stop_words_and_unicode() {
+        assert_eq!(query_terms("JWT Refresh"), vec!["jwt", "refresh"]);
+        assert_eq!(
+            query_terms(r#"cookie "refresh token" auth"#),
+            vec!["refresh token", "cookie", "auth"]
+        );
+        assert_eq!(query_terms("the key to the cache"), vec!["key", "cache"]);
+        assert_eq!(query_terms("to be"), vec!["to", "be"]);
+        assert_eq!(query_terms(r#"auth "half open"#), vec!["half open", "auth"]);
+        assert_eq!(query_terms("CAFÉ Ünïcode"), vec!["café", "ünïcode"]);
+        assert!(query_terms(r#""""#).is_empty());
+    }
+
+    #[test]
+    fn search_hits_carry_source_path_and_line_number() {
+        let entries = [IndexEntry {
+            project: "proj".to_string(),
+            path: "/proj/MEMORY.md".to_string(),
+            content: "# proj\n\n- Rejected OAuth (too complex)".to_string(),
+            mtime: 0,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        }];
+        let hits = search_index(&entries, "oauth");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].project, "proj");
+        assert_eq!(hits[0].path, "/proj/MEMORY.md");
+        assert_eq!(hits[0].line_no, Some(3));
+    }
+
+    #[test]
+    fn config_missing_keys_use_defaults() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.token_budget, DEFAULT_TOKEN_BUDGET);
+        let config: Config = serde_json::from_str(r#"{"token_budget":500}"#).unwrap();
+        assert_eq!(config.token_budget, 500);
+        assert_eq!(config.sections, SectionKind::DEFAULT_ORDER.to_vec());
+    }
+
+    #[test]
+    fn refresh_entry_ignores_mtime_only_changes() {
+        let mut entry = IndexEntry {
+            project: "proj".to_string(),
+            path: "/proj/MEMORY.md".to_string(),
+            content: "- a\n- b".to_string(),
+            mtime: 1,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        };
+        assert!(!refresh_entry(&mut entry, "- a\n- b".to_string(), 2, 2));
+        assert_eq!(entry.mtime, 2);
+        assert_eq!(entry.prev_lines, None);
+
+        assert!(refresh_entry(&mut entry, "- a\n- b\n- c".to_string(), 3, 3));
+        assert_eq!(entry.mtime, 3);
+        assert_eq!(entry.prev_lines, Some(2));
+        assert_eq!(entry.content, "- a\n- b\n- c");
+    }
+
+    #[test]
+    fn refresh_entry_tracks_source_lines_of_truncated_files() {
+        let excerpt = format!("- a\n{}\n- z", truncation_marker(998));
+        let mut entry = IndexEntry {
+            project: "huge".to_string(),
+            path: "/huge/MEMORY.md".to_string(),
+            content: excerpt.clone(),
+            mtime: 1,
+            prev_lines: None,
+            truncated: true,
+            lines: Some(1000),
+        };
+        // An edit in the cut middle leaves the excerpt as it was
+        assert!(refresh_entry(&mut entry, excerpt, 1200, 2));
+        assert_eq!(entry.prev_lines, Some(1000));
+        assert_eq!(entry.line_count(), 1200);
+
+        let report = format_index_report(&[entry], 2, false, Lang::En);
+        assert!(report.contains("1200 (+200)"));
+    }
+
+    #[test]
+    fn index_report_screen_reader_uses_sentences() {
+        let entries = [IndexEntry {
+            project: "big".to_string(),
+            path: "/big/MEMORY.md".to_string(),
+            content: (0..40).map(|i| format!("- {i}\n")).collect(),
+            mtime: 0,
+            prev_lines: Some(28),
+            truncated: false,
+            lines: None,
+        }];
+        assert_eq!(
+            format_index_report(&entries, 2 * 86_400, true, Lang::En),
+            "big: modified 2d ago, 40 lines, +12 since last index, over 30-line guidance\n"
+        );
+    }
+
+    #[test]
+    fn search_hits_screen_reader_has_no_box_drawing() {
+        let hits = [SearchHit {
+            project: "proj",
+            path: "/proj/MEMORY.md",
+            line_no: Some(3),
+            line: "- Used JWT",
+        }];
+        assert_eq!(
+            format_search_hits(&hits, false, Lang::En),
+            "── proj ── /proj/MEMORY.md\n    3: - Used JWT\n\n"
+        );
+        let plain = format_search_hits(&hits, true, Lang::En);
+        assert_eq!(
+            plain,
+            "Project proj, file /proj/MEMORY.md\nLine 3: - Used JWT\n\n"
+        );
+        assert!(!plain.contains('─'));
+    }
+
+    #[test]
+    fn plain_text_replaces_symbols() {
+        assert_eq!(
+            plain_text("NOT installed — run `mem init`"),
+            "NOT installed; run `mem init`"
+        );
+    }
+
+    #[test]
+    fn format_age_picks_largest_unit() {
+        assert_eq!(format_age(90), "1m");
+        assert_eq!(format_age(7200), "2h");
+        assert_eq!(format_age(3 * 86_400 + 5), "3d");
+        assert_eq!(format_age(-10), "0m");
+    }
+
+    #[test]
+    fn index_report_shows_trend_and_flags_long_files() {
+        let long = (0..40)
+            .map(|i| format!("- {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let entries = [
+            IndexEntry {
+                project: "big".to_string(),
+                path: "/big/MEMORY.md".to_string(),
+                content: long,
+                mtime: 0,
+                prev_lines: Some(28),
+                truncated: false,
+                lines: None,
+            },
+            IndexEntry {
+                project: "small".to_string(),
+                path: "/small/MEMORY.md".to_string(),
+                content: "- one\n- two".to_string(),
+                mtime: 86_400,
+                prev_lines: None,
+                truncated: false,
+                lines: None,
+            },
+        ];
+        let report = format_index_report(&entries, 2 * 86_400, false, Lang::En);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("big"));
+        assert!(lines[1].contains("2d"));
+        assert!(lines[1].contains("40 (+12)"));
+        assert!(lines[1].ends_with("over 30-line guidance"));
+        assert!(lines[2].starts_with("small"));
+        assert!(lines[2].contains("1d"));
+        assert!(!lines[2].contains("guidance"));
+    }
+
+    #[test]
+    fn truncate_head_tail_leaves_small_content_alone() {
+        assert_eq!(truncate_head_tail("- a\n- b", 10, 1024), "- a\n- b");
+        assert_eq!(truncate_head_tail("- a\n- b", 0, 0), "- a\n- b");
+    }
+
+    #[test]
+    fn truncate_head_tail_caps_lines_keeping_both_ends() {
+        let content = (1..=100)
+            .map(|i| format!("- {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let out = truncate_head_tail(&content, 10, 0);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "- 1");
+        assert_eq!(lines[5], "[truncated 91 lines — run `mem show-file`]");
+        assert_eq!(lines[9], "- 100");
+    }
+
+    #[test]
+    fn truncate_head_tail_caps_bytes() {
+        let content = (1..=100)
+            .map(|i| format!("- line {i:03}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let out = truncate_head_tail(&content, 0, 100);
+        assert!(out.contains("[truncated"));
+        assert!(out.starts_with("- line 001"));
+        assert!(out.ends_with("- line 100"));
+        assert!(out.len() <= 100, "{} bytes", out.len());
+    }
+
+    #[test]
+    fn truncate_head_tail_keeps_middle_headings() {
+        let mut lines: Vec<String> = (1..=60).map(|i| format!("- {i}")).collect();
+        lines[20] = "## Auth".to_string();
+        lines[40] = "## Deploy".to_string();
+        let out = truncate_head_tail(&lines.join("\n"), 12, 0);
+        let out: Vec<&str> = out.lines().collect();
+        assert_eq!(out.len(), 12);
+        assert_eq!(out[0], "- 1");
+        assert_eq!(out[5], "[truncated 49 lines — run `mem show-file`]");
+        assert_eq!(out[6..8], ["## Auth", "## Deploy"]);
+        assert_eq!(out[11], "- 60");
+    }
+
+    #[test]
+    fn read_for_index_caps_large_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("MEMORY.md");
+        let content = (1..=500)
+            .map(|i| format!("- decision {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&path, &content).unwrap();
+
+        let (full, lines, truncated) = read_for_index(&path, 0).unwrap();
+        assert!(!truncated);
+        assert_eq!(full, content);
+        assert_eq!(lines, 500);
+
+        let (capped, lines, truncated) = read_for_index(&path, 1024).unwrap();
+        assert!(truncated);
+        assert_eq!(lines, 500);
+        assert!(capped.starts_with("- decision 1\n"));
+        assert!(capped.ends_with("- decision 500"));
+        assert!(capped.contains("[truncated"));
+    }
+
+    #[test]
+    fn one_line_summary_skips_headings_and_bullets() {
+        assert_eq!(
+            one_line_summary("# myapp\n\n- Auth: JWT, not sessions\n- other").as_deref(),
+            Some("Auth: JWT, not sessions")
+        );
+        assert_eq!(one_line_summary("# only a title\n"), None);
+        let long = format!("- {}", "x".repeat(150));
+        assert_eq!(
+            one_line_summary(&long).unwrap().chars().count(),
+            SUMMARY_CHARS + 1
+        );
+    }
+
+    #[test]
+    fn recent_project_lines_lists_newest_first() {
+        let entry = |project: &str, mtime: i64| IndexEntry {
+            project: project.to_string(),
+            path: format!("/{project}/MEMORY.md"),
+            content: format!("# {project}\n- note for {project}"),
+            mtime,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        };
+        let index = [entry("old", 0), entry("new", 7200), entry("mid", 3600)];
+        assert_eq!(
+            recent_project_lines(&index, 7200, 2),
+            vec![
+                "- new (0m ago): note for new",
+                "- mid (1h ago): note for mid"
+            ]
+        );
+        assert!(recent_project_lines(&[], 0, 5).is_empty());
+    }
+
+    #[test]
+    fn push_hook_run_keeps_newest() {
+        let run = |started_at: i64| HookRun {
+            hook: "session-start".to_string(),
+            started_at,
+            duration_ms: 1,
+            exit_code: 0,
+            error: None,
+        };
+        let mut runs: Vec<HookRun> = (0..HOOK_RUNS_KEPT as i64).map(run).collect();
+        push_hook_run(&mut runs, run(1000));
+        assert_eq!(runs.len(), HOOK_RUNS_KEPT);
+        assert_eq!(runs[0].started_at, 1);
+        assert_eq!(runs.last().unwrap().started_at, 1000);
+    }
+
+    #[test]
+    fn format_hook_runs_shows_failures_newest_first() {
+        let runs = [
+            HookRun {
+                hook: "session-start".to_string(),
+                started_at: 0,
+                duration_ms: 12,
+                exit_code: 0,
+                error: None,
+            },
+            HookRun {
+                hook: "session-start".to_string(),
+                started_at: 3600,
+                duration_ms: 3,
+                exit_code: 1,
+                error: Some("read stdin: broken pipe".to_string()),
+            },
+        ];
+        let out = format_hook_runs(&runs, 7200, false, Lang::En);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "Recent hook runs:");
+        assert!(lines[1].contains("1h ago") && lines[1].ends_with("FAILED (exit 1)"));
+        assert_eq!(lines[2].trim(), "read stdin: broken pipe");
+        assert!(lines[3].contains("2h ago") && lines[3].ends_with("12ms  ok"));
+        assert_eq!(
+            format_hook_runs(&[], 0, false, Lang::En),
+            "No hook runs recorded yet.\n"
+        );
+
+        assert_eq!(
+            format_hook_runs(&runs, 7200, true, Lang::En),
+            "Recent hook runs:\n\
+             session-start ran 1h ago, took 3 ms, FAILED (exit 1)\n\
+             Error: read stdin: broken pipe\n\
+             session-start ran 2h ago, took 12 ms, ok\n"
+        );
+    }
+
+    #[test]
+    fn render_export_markdown_has_section_per_project() {
+        let a = IndexEntry {
+            project: "api".to_string(),
+            path: "/a/MEMORY.md".to_string(),
+            content: "- JWT\n".to_string(),
+            mtime: 5,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        };
+        let b = IndexEntry {
+            project: "web".to_string(),
+            path: "/b/MEMORY.md".to_string(),
+            content: "- SSR".to_string(),
+            mtime: 6,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        };
+        assert_eq!(
+            render_export(&[&a, &b], ExportFormat::Markdown, &Ownership::default()).unwrap(),
+            "## api\n\n_Source: `/a/MEMORY.md`_\n\n- JWT\n\n## web\n\n_Source: `/b/MEMORY.md`_\n\n- SSR\n"
+        );
+    }
+
+    #[test]
+    fn render_export_json_is_portable() {
+        let a = IndexEntry {
+            project: "api".to_string(),
+            path: "/a/MEMORY.md".to_string(),
+            content: "- JWT".to_string(),
+            mtime: 5,
+            prev_lines: Some(1),
+            truncated: false,
+            lines: None,
+        };
+        let json = render_export(&[&a], ExportFormat::Json, &Ownership::default()).unwrap();
+        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            val,
+            serde_json::json!([{
+                "project": "api",
+                "source_path": "/a/MEMORY.md",
+                "modified_at": 5,
+                "content": "- JWT"
+            }])
+        );
+    }
+
+    #[test]
+    fn import_entries_remaps_and_skips_conflicts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_str().unwrap();
+        let same = tmp.path().join("same/MEMORY.md");
+        let differs = tmp.path().join("differs/MEMORY.md");
+        std::fs::create_dir_all(same.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(differs.parent().unwrap()).unwrap();
+        std::fs::write(&same, "- JWT").unwrap();
+        std::fs::write(&differs, "- local edit").unwrap();
+
+        let record = |name: &str, content: &str| ImportEntry {
+            project: name.to_string(),
+            source_path: format!("/old-home/{name}/MEMORY.md"),
+            content: content.to_string(),
+        };
+        let records = [
+            record("same", "- JWT"),
+            record("differs", "- archived"),
+            record("fresh", "- SSR"),
+        ];
+        let remap = [("/old-home".to_string(), root.to_string())];
+
+        let projects = tmp.path().join("projects");
+
+        let stats = import_entries(&records, &remap, false, &projects).unwrap();
+        assert_eq!(
+            stats,
+            ImportStats {
+                new: 1,
+                updated: 0,
+                skipped: 2,
+                rejected: 0
+            }
+        );
+        let fresh = tmp.path().join("fresh/MEMORY.md");
+        assert_eq!(std::fs::read_to_string(fresh).unwrap(), "- SSR");
+        assert_eq!(std::fs::read_to_string(&differs).unwrap(), "- local edit");
+
+        let stats = import_entries(&records, &remap, true, &projects).unwrap();
+        assert_eq!(
+            stats,
+            ImportStats {
+                new: 0,
+                updated: 1,
+                skipped: 2,
+                rejected: 0
+            }
+        );
+        assert_eq!(std::fs::read_to_string(&differs).unwrap(), "- archived");
+    }
+
+    #[test]
+    fn import_target_rejects_paths_outside_project_memory() {
+        let projects = Path::new("/home/u/.claude/projects");
+        let target = |source: &str| import_target(source, &[], projects);
+
+        assert_eq!(
+            target("/home/u/.claude/projects/-home-u-api/memory/MEMORY.md"),
+            Ok(PathBuf::from(
+                "/home/u/.claude/projects/-home-u-api/memory/MEMORY.md"
+            ))
+        );
+        for evil in [
+            "/home/u/evil/.bashrc",
+            "/home/u/evil/MEMORY.md",
+            "/home/u/.claude/projects/-x/memory/../../../../.bashrc",
+            "/home/u/.claude/projects/-x/../-y/memory/MEMORY.md",
+            "/home/u/.claude/projects/-x/memory/notes.md",
+            "/home/u/.claude/projects/-x/memory/deep/MEMORY.md",
+            "relative/memory/MEMORY.md",
+        ] {
+            assert!(target(evil).is_err(), "{evil}");
+        }
+
+        // An explicit remap may place MEMORY.md anywhere, but not escape it.
+        let remap = [("/old".to_string(), "/srv/notes".to_string())];
+        assert_eq!(
+            import_target("/old/api/MEMORY.md", &remap, projects),
+            Ok(PathBuf::from("/srv/notes/api/MEMORY.md"))
+        );
+        assert!(import_target("/old/../etc/MEMORY.md", &remap, projects).is_err());
+        assert!(import_target("/old/api/.bashrc", &remap, projects).is_err());
+        // Whole components only: /old does not match /oldies.
+        assert!(import_target("/oldies/api/MEMORY.md", &remap, projects).is_err());
+    }
+
+    #[test]
+    fn resolve_index_path_prefers_flag_then_env_then_home() {
+        let home = Some(PathBuf::from("/home/u"));
+        assert_eq!(
+            resolve_index_path(None, None, home.clone()),
+            Some(PathBuf::from("/home/u/.mem/index.json"))
+        );
+        assert_eq!(
+            resolve_index_path(None, Some("".into()), home.clone()),
+            Some(PathBuf::from("/home/u/.mem/index.json"))
+        );
+        assert_eq!(
+            resolve_index_path(None, Some("/sync/work.json".into()), home.clone()),
+            Some(PathBuf::from("/sync/work.json"))
+        );
+        assert_eq!(
+            resolve_index_path(
+                Some(PathBuf::from("/tmp/personal.json")),
+                Some("/sync/work.json".into()),
+                home
+            ),
+            Some(PathBuf::from("/tmp/personal.json"))
+        );
+        assert_eq!(resolve_index_path(None, None, None), None);
+    }
+
+    #[test]
+    fn render_export_stamps_ownership() {
+        let a = IndexEntry {
+            project: "api".to_string(),
+            path: "/a/MEMORY.md".to_string(),
+            content: "- JWT".to_string(),
+            mtime: 5,
+            prev_lines: None,
+            truncated: false,
+            lines: None,
+        };
+        let owner = Ownership {
+            author: Some("Ana".to_string()),
+            org: None,
+            license: Some("CC-BY-4.0".to_string()),
+        };
+        let json = render_export(&[&a], ExportFormat::Json, &owner).unwrap();
+        let val: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(val[0]["author"], "Ana");
+        assert_eq!(val[0]["license"], "CC-BY-4.0");
+        assert!(val[0].get("org").is_none());
+        assert_eq!(
+            render_export(&[&a], ExportFormat::Markdown, &owner).unwrap(),
+            "## api\n\n_Source: `/a/MEMORY.md`_\n\n_Author: Ana · License: CC-BY-4.0_\n\n- JWT\n"
+        );
+
+        let manifest =
+            serde_json::to_value(export_manifest(&[&a], ExportFormat::Markdown, &owner, 100))
+                .unwrap();
+        assert_eq!(manifest["generated_at"], 100);
+        assert_eq!(manifest["author"], "Ana");
+        assert!(manifest.get("org").is_none());
+        assert_eq!(
+            manifest["files"],
+            serde_json::json!([{
+                "project": "api",
+                "file": "api.md",
+                "source_path": "/a/MEMORY.md",
+                "modified_at": 5,
+                "lines": 1
+            }])
+        );
+    }
+
+    #[test]
+    fn scheduled_job_is_replaced_not_duplicated() {
+        let user = "MAILTO=me\n30 2 * * * backup.sh\n";
+        let once = with_scheduled_job(user, "0 9 * * 1 mem index # mem schedule");
+        let twice = with_scheduled_job(&once, "0 9 * * 1 /new/mem index # mem schedule");
+        assert_eq!(
+            twice,
+            "MAILTO=me\n30 2 * * * backup.sh\n0 9 * * 1 /new/mem index # mem schedule\n"
+        );
+        assert_eq!(
+            scheduled_job(&twice),
+            Some("0 9 * * 1 /new/mem index # mem schedule")
+        );
+        assert_eq!(without_scheduled_job(&twice), user);
+        assert_eq!(scheduled_job(user), None);
+    }
+
+    #[test]
+    fn cron_quote_escapes_quotes_and_percent() {
+        assert_eq!(cron_quote("/home/a b/mem"), "'/home/a b/mem'");
+        assert_eq!(cron_quote("it's"), r"'it'\''s'");
+        assert_eq!(
+            cron_quote("/data/100%/index.json"),
+            r"'/data/100\%/index.json'"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn copy_with_falls_through_to_a_working_tool() {
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("clip.txt");
+        let out = out.to_str().unwrap();
+        let commands: &[(&str, &[&str])] = &[
+            ("mem-test-no-such-clipboard", &[]),
+            ("false", &[]),
+            ("sh", &["-c", "exec 0<&-; exit 1"]),
+            ("tee", &[out]),
+        ];
+        assert_eq!(copy_with("- JWT", commands).unwrap(), "tee");
+        assert_eq!(std::fs::read_to_string(out).unwrap(), "- JWT");
+
+        assert!(copy_with("- JWT", &commands[..3]).is_err());
+    }
+
+    #[test]
+    fn truncated_entries_search_without_line_numbers() {
+        let entry = IndexEntry {
+            project: "huge".to_string(),
+            path: "/huge/MEMORY.md".to_string(),
+            content: format!("- decision 1\n{}\n- decision 8000", truncation_marker(7998)),
+            mtime: 0,
+            prev_lines: None,
+            truncated: true,
+            lines: None,
+        };
+        let index = [entry];
+        let hits = search_index(&index, "decision");
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| h.line_no.is_none()));
+        assert!(search_index(&index, "truncated").is_empty());
+
+        let out = format_search_hits(&hits, false, Lang::En);
+        assert!(out.contains("line numbers omitted"));
+        assert!(out.contains("       - decision 8000\n"));
+    }
+
+    #[test]
+    fn search_reads_source_of_truncated_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("MEMORY.md");
+        std::fs::write(&path, "- head\n- middle: kubernetes\n- tail").unwrap();
+        let entry = IndexEntry {
+            project: "p".to_string(),
+            path: path.to_string_lossy().to_string(),
+            content: format!("- head\n{}\n- tail", truncation_marker(1)),
+            mtime: 0,
+            prev_lines: None,
+            truncated: true,
+            lines: Some(3),
+        };
+        let mut index = [entry.clone(), entry];
+        index[1].path = tmp.path().join("gone.md").to_string_lossy().to_string();
+
+        assert_eq!(expand_truncated(&mut index), 1);
+        let hits = search_index(&index, "kubernetes");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line_no, Some(2));
+        assert_eq!(
+            suggest_query(&index, "kubernetis").as_deref(),
+            Some("kubernetes")
+        );
+        assert!(index[1].truncated);
+    }
+
+    #[test]
+    fn export_reads_source_of_truncated_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("MEMORY.md");
+        std::fs::write(&path, "- a\n- b\n- c").unwrap();
+        let mut entry = IndexEntry {
+            project: "p".to_string(),
+            path: path.to_string_lossy().to_string(),
+            content: format!("- a\n{}", truncation_marker(2)),
+            mtime: 0,
+            prev_lines: None,
+            truncated: true,
+            lines: None,
+        };
+        assert_eq!(full_entry(&entry).unwrap().content, "- a\n- b\n- c");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(full_entry(&entry).is_err());
+
+        entry.truncated = false;
+        assert_eq!(full_entry(&entry).unwrap().content, entry.content);
+    }
+
+    #[test]
+    fn import_rejects_truncated_excerpts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let projects = tmp.path().join("projects");
+        let target = projects.join("-p/memory/MEMORY.md");
+        let records = [ImportEntry {
+            project: "p".to_string(),
+            source_path: target.to_string_lossy().to_string(),
+            content: format!("- a\n{}\n- z", truncation_marker(40)),
+        }];
+        let stats = import_entries(&records, &[], true, &projects).unwrap();
+        assert_eq!(stats.rejected, 1);
+        assert!(!target.exists());
     }
 }